@@ -593,12 +593,19 @@ pub fn pb_value_to_value_ref<'a>(
                 .as_ref()
                 .and_then(|column_ext| column_ext.type_ext.as_ref())
             {
-                ValueRef::Decimal128(Decimal128::from_value_precision_scale(
-                    v.hi,
-                    v.lo,
-                    d.precision as u8,
-                    d.scale as i8,
-                ))
+                // The precision and scale come from the request, map the value to null
+                // if they are invalid.
+                let decimal = u8::try_from(d.precision)
+                    .ok()
+                    .zip(i8::try_from(d.scale).ok())
+                    .and_then(|(precision, scale)| {
+                        Decimal128::try_from_value_precision_scale(v.hi, v.lo, precision, scale)
+                            .ok()
+                    });
+                match decimal {
+                    Some(decimal) => ValueRef::Decimal128(decimal),
+                    None => ValueRef::Null,
+                }
             } else {
                 // If the precision and scale are not set, use the default value.
                 ValueRef::Decimal128(Decimal128::from_value_precision_scale(
@@ -2041,4 +2048,47 @@ mod tests {
         assert_eq!(pb_decimal.lo, 123);
         assert_eq!(pb_decimal.hi, 0);
     }
+
+    #[test]
+    fn test_pb_decimal128_value_to_value_ref() {
+        let value = v1::Value {
+            value_data: Some(ValueData::Decimal128Value(v1::Decimal128 {
+                hi: 0,
+                lo: 123,
+            })),
+        };
+        let ext = |precision, scale| {
+            Some(ColumnDataTypeExtension {
+                type_ext: Some(TypeExt::DecimalType(DecimalTypeExtension {
+                    precision,
+                    scale,
+                })),
+            })
+        };
+
+        let ext_10_2 = ext(10, 2);
+        assert_eq!(
+            ValueRef::Decimal128(Decimal128::new(123, 10, 2)),
+            pb_value_to_value_ref(&value, &ext_10_2)
+        );
+        // Invalid precision or scale is mapped to null.
+        for invalid in [
+            ext(0, 0),
+            ext(39, 2),
+            ext(10, 11),
+            ext(266, 2),
+            ext(10, 130),
+        ] {
+            assert_eq!(ValueRef::Null, pb_value_to_value_ref(&value, &invalid));
+        }
+        // Unset precision and scale fall back to the default.
+        assert_eq!(
+            ValueRef::Decimal128(Decimal128::new(
+                123,
+                DECIMAL128_MAX_PRECISION,
+                DECIMAL128_DEFAULT_SCALE,
+            )),
+            pb_value_to_value_ref(&value, &None)
+        );
+    }
 }
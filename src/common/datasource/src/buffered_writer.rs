@@ -32,6 +32,8 @@ pub struct LazyBufferedWriter<T, U, F> {
     buffer: SharedBuffer,
     rows_written: usize,
     bytes_written: u64,
+    /// CRC32C checksum of the bytes written to the underlying writer.
+    checksum: u32,
     threshold: usize,
 }
 
@@ -53,7 +55,9 @@ impl<
 {
     /// Closes `LazyBufferedWriter` and optionally flushes all data to underlying storage
     /// if any row's been written.
-    pub async fn close_with_arrow_writer(mut self) -> Result<(FileMetaData, u64)> {
+    ///
+    /// Returns the file metadata, bytes written and the CRC32C checksum of the bytes written.
+    pub async fn close_with_arrow_writer(mut self) -> Result<(FileMetaData, u64, u32)> {
         let encoder = self
             .encoder
             .take()
@@ -68,7 +72,7 @@ impl<
         }
         // It's important to shut down! flushes all pending writes
         self.close_inner_writer().await?;
-        Ok((metadata, self.bytes_written))
+        Ok((metadata, self.bytes_written, self.checksum))
     }
 }
 
@@ -101,6 +105,7 @@ impl<
             buffer,
             rows_written: 0,
             bytes_written: 0,
+            checksum: 0,
             writer_factory,
             writer: None,
         }
@@ -134,6 +139,7 @@ impl<
                 .write_all(&chunk)
                 .await
                 .context(error::AsyncWriteSnafu)?;
+            self.checksum = crc32c::crc32c_append(self.checksum, &chunk);
 
             bytes_written += size as u64;
         }
@@ -162,6 +168,7 @@ impl<
             .write_all(&remain)
             .await
             .context(error::AsyncWriteSnafu)?;
+        self.checksum = crc32c::crc32c_append(self.checksum, &remain);
         Ok(size as u64)
     }
 }
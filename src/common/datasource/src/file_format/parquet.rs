@@ -223,8 +223,8 @@ impl BufferedWriter {
 
     /// Close parquet writer.
     ///
-    /// Return file metadata and bytes written.
-    pub async fn close(self) -> error::Result<(FileMetaData, u64)> {
+    /// Return file metadata, bytes written and the CRC32C checksum of the bytes written.
+    pub async fn close(self) -> error::Result<(FileMetaData, u64, u32)> {
         self.inner.close_with_arrow_writer().await
     }
 }
@@ -264,10 +264,12 @@ pub async fn stream_to_parquet(
 
 #[cfg(test)]
 mod tests {
+    use arrow::array::{Int64Array, StringArray};
     use common_test_util::find_workspace_path;
+    use common_test_util::temp_dir::create_temp_dir;
 
     use super::*;
-    use crate::test_util::{format_schema, test_store};
+    use crate::test_util::{format_schema, test_basic_schema, test_store};
 
     fn test_data_root() -> String {
         find_workspace_path("/src/common/datasource/tests/parquet")
@@ -284,4 +286,40 @@ mod tests {
 
         assert_eq!(vec!["num: Int64: NULL", "str: Utf8: NULL"], formatted);
     }
+
+    #[tokio::test]
+    async fn test_buffered_writer_checksum() {
+        let dir = create_temp_dir("test_buffered_writer_checksum");
+        let store = test_store(dir.path().to_str().unwrap());
+        let schema = test_basic_schema();
+        // A small threshold to flush the buffer in multiple chunks.
+        let mut writer = BufferedWriter::try_new(
+            "test.parquet".to_string(),
+            store.clone(),
+            schema.clone(),
+            None,
+            64,
+            1,
+        )
+        .await
+        .unwrap();
+        for i in 0..10 {
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int64Array::from_iter_values(i * 100..(i + 1) * 100)),
+                    Arc::new(StringArray::from_iter_values(
+                        (0..100).map(|j| format!("value-{i}-{j}")),
+                    )),
+                ],
+            )
+            .unwrap();
+            writer.write(&batch).await.unwrap();
+        }
+        let (_, file_size, checksum) = writer.close().await.unwrap();
+
+        let data = store.read("test.parquet").await.unwrap();
+        assert_eq!(data.len() as u64, file_size);
+        assert_eq!(crc32c::crc32c(&data), checksum);
+    }
 }
@@ -16,14 +16,15 @@ use std::fmt::Display;
 use std::hash::Hash;
 use std::str::FromStr;
 
+use bigdecimal::num_bigint::BigInt;
 use bigdecimal::{BigDecimal, ToPrimitive};
 use rust_decimal::Decimal as RustDecimal;
 use serde::{Deserialize, Serialize};
-use snafu::ResultExt;
+use snafu::{OptionExt, ResultExt};
 
 use crate::error::{
-    self, BigDecimalOutOfRangeSnafu, Error, InvalidPrecisionOrScaleSnafu, ParseBigDecimalStrSnafu,
-    ParseRustDecimalStrSnafu,
+    self, BigDecimalOutOfRangeSnafu, DecimalOverflowSnafu, Error, InvalidPrecisionOrScaleSnafu,
+    ParseBigDecimalStrSnafu, ParseRustDecimalStrSnafu,
 };
 
 /// The maximum precision for [Decimal128] values
@@ -72,6 +73,12 @@ impl Decimal128 {
 
     /// Try new Decimal128 from i128, precision and scale with validation.
     pub fn try_new(value: i128, precision: u8, scale: i8) -> error::Result<Self> {
+        Self::from_components(value, precision, scale)
+    }
+
+    /// Create a new Decimal128 from the components with validation, all the validated
+    /// constructors delegate to this to keep the validation in one place.
+    fn from_components(value: i128, precision: u8, scale: i8) -> error::Result<Self> {
         // make sure the precision and scale is valid.
         valid_precision_and_scale(precision, scale)?;
         Ok(Self {
@@ -112,14 +119,162 @@ impl Decimal128 {
     /// Convert from precision, scale, a i128 value which
     /// represents by i64 + i64 value(high-64 bit, low-64 bit).
     pub fn from_value_precision_scale(hi: i64, lo: i64, precision: u8, scale: i8) -> Self {
+        Self::new(Self::join_value(hi, lo), precision, scale)
+    }
+
+    /// Same as [Decimal128::from_value_precision_scale], but validates the precision
+    /// and scale, which may come from untrusted protobuf values.
+    pub fn try_from_value_precision_scale(
+        hi: i64,
+        lo: i64,
+        precision: u8,
+        scale: i8,
+    ) -> error::Result<Self> {
+        Self::from_components(Self::join_value(hi, lo), precision, scale)
+    }
+
+    /// Join the high-64 bit and low-64 bit into the i128 value.
+    fn join_value(hi: i64, lo: i64) -> i128 {
         // 128                             64                              0
         // +-------+-------+-------+-------+-------+-------+-------+-------+
         // |               hi              |               lo              |
         // +-------+-------+-------+-------+-------+-------+-------+-------+
         let hi = (hi as u128 & u64::MAX as u128) << 64;
         let lo = lo as u128 & u64::MAX as u128;
-        let value = (hi | lo) as i128;
-        Self::new(value, precision, scale)
+        (hi | lo) as i128
+    }
+
+    /// Create a new Decimal128 from the 16 bytes little-endian two's complement value,
+    /// which is the layout of decimal128 in arrow buffers, without any validation.
+    pub fn from_le_bytes(bytes: &[u8; 16], precision: u8, scale: i8) -> Self {
+        Self::new(i128::from_le_bytes(*bytes), precision, scale)
+    }
+
+    /// Convert the value to 16 bytes little-endian two's complement, the precision
+    /// and scale information is discarded.
+    pub fn to_le_bytes(&self) -> [u8; 16] {
+        self.value.to_le_bytes()
+    }
+
+    /// Convert to the unscaled digits string (with sign) and the scale,
+    /// the decimal value equals `unscaled * 10^(-scale)`.
+    ///
+    /// For example:
+    /// 1234.56 (value 123456, scale 2) returns ("123456", 2).
+    pub fn to_unscaled_str(&self) -> (String, i8) {
+        (self.value.to_string(), self.scale)
+    }
+
+    /// Rescale the decimal to `new_scale`, keeping the precision unchanged.
+    ///
+    /// Increasing the scale multiplies the underlying value, decreasing it truncates
+    /// the extra fractional digits toward zero.
+    /// Return error if the new scale is invalid or the rescaled value overflows the precision.
+    ///
+    /// For example:
+    /// 1.23 (precision 5, scale 2) rescaled to scale 4 is 1.2300 (value 12300).
+    pub fn rescale(&self, new_scale: i8) -> error::Result<Self> {
+        valid_precision_and_scale(self.precision, new_scale)?;
+
+        let diff = new_scale as i32 - self.scale as i32;
+        let value = if diff >= 0 {
+            10_i128
+                .checked_pow(diff as u32)
+                .and_then(|factor| self.value.checked_mul(factor))
+        } else {
+            // A divisor larger than i128::MAX truncates every value to zero.
+            Some(
+                10_i128
+                    .checked_pow(diff.unsigned_abs())
+                    .map_or(0, |factor| self.value / factor),
+            )
+        };
+
+        let value = value
+            .filter(|v| v.unsigned_abs() < 10_u128.pow(self.precision as u32))
+            .context(DecimalOverflowSnafu {
+                value: self.value,
+                precision: self.precision,
+                scale: new_scale,
+            })?;
+
+        Ok(Self {
+            value,
+            precision: self.precision,
+            scale: new_scale,
+        })
+    }
+
+    /// Formats the decimal like [Display], but inserts the `separator` every three
+    /// digits in the integer part, e.g. 1234567.89 is formatted as `1,234,567.89`.
+    pub fn to_string_grouped(&self, separator: char) -> String {
+        let s = self.to_string();
+        let (sign, unsigned) = match s.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", s.as_str()),
+        };
+        let (int_part, frac_part) = match unsigned.split_once('.') {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (unsigned, None),
+        };
+
+        let mut grouped = String::with_capacity(s.len() + int_part.len() / 3);
+        grouped.push_str(sign);
+        for (i, c) in int_part.chars().enumerate() {
+            if i > 0 && (int_part.len() - i) % 3 == 0 {
+                grouped.push(separator);
+            }
+            grouped.push(c);
+        }
+        if let Some(frac_part) = frac_part {
+            grouped.push('.');
+            grouped.push_str(frac_part);
+        }
+        grouped
+    }
+
+    /// Returns true if the difference between two decimals is no larger than the
+    /// absolute value of `epsilon`. Unlike the [PartialOrd], the decimals are compared
+    /// by their values, regardless of their precision and scale.
+    ///
+    /// For example, 1.000 and 1.001 are approximately equal with epsilon 0.01.
+    pub fn approx_eq(&self, other: &Decimal128, epsilon: Decimal128) -> bool {
+        let diff = self.to_big_decimal() - other.to_big_decimal();
+        diff.abs() <= epsilon.to_big_decimal().abs()
+    }
+
+    fn to_big_decimal(self) -> BigDecimal {
+        BigDecimal::new(BigInt::from(self.value), self.scale as i64)
+    }
+
+    /// Returns the number of characters that [Display] produces for the decimal,
+    /// including the sign and the decimal point, without formatting it.
+    pub fn display_width(&self) -> usize {
+        let sign = (self.value < 0) as usize;
+        let digits = self
+            .value
+            .unsigned_abs()
+            .checked_ilog10()
+            .map_or(1, |d| d as usize + 1);
+        // Keep the same truncation as `format_decimal_str`.
+        let kept = digits.min(self.precision as usize);
+
+        if self.scale == 0 {
+            sign + kept
+        } else if self.scale < 0 {
+            sign + kept + self.scale.unsigned_abs() as usize
+        } else if digits > self.scale as usize {
+            sign + kept + 1
+        } else {
+            // Padded to "0.<scale digits>"
+            sign + 2 + self.scale as usize
+        }
+    }
+
+    /// Convert to RustDecimal, return `None` if the value is out of the range
+    /// that RustDecimal can represent.
+    pub fn to_rust_decimal(&self) -> Option<RustDecimal> {
+        RustDecimal::try_from_i128_with_scale(self.value, self.scale as u32).ok()
     }
 }
 
@@ -172,6 +327,14 @@ impl FromStr for Decimal128 {
 
 impl Display for Decimal128 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // fast path: integers that fit the precision are formatted without allocation.
+        if self.scale == 0
+            && 10_u128
+                .checked_pow(self.precision as u32)
+                .map_or(true, |bound| self.value.unsigned_abs() < bound)
+        {
+            return write!(f, "{}", self.value);
+        }
         write!(
             f,
             "{}",
@@ -310,8 +473,48 @@ fn valid_precision_and_scale(precision: u8, scale: i8) -> error::Result<()> {
 
 #[cfg(test)]
 mod tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::fmt::Write;
+
     use super::*;
 
+    thread_local! {
+        /// The number of allocations made by the current thread.
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// An allocator that counts the allocations of each thread, so tests running
+    /// in parallel don't affect each other.
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            // The thread local may be unavailable while the thread is exiting.
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    /// Returns the number of allocations made by `f` on the current thread.
+    fn count_allocations(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
     #[test]
     fn test_common_decimal128() {
         let decimal = Decimal128::new(123456789, 9, 3);
@@ -461,4 +664,231 @@ mod tests {
         test_decimal128_eq(i128::MAX);
         test_decimal128_eq(i128::MIN);
     }
+
+    #[test]
+    fn test_decimal128_rescale() {
+        let decimal = Decimal128::new(123, 5, 2);
+        let rescaled = decimal.rescale(4).unwrap();
+        assert_eq!(12300, rescaled.val());
+        assert_eq!(5, rescaled.precision());
+        assert_eq!(4, rescaled.scale());
+        assert_eq!("1.2300", rescaled.to_string());
+
+        // truncate toward zero
+        let decimal = Decimal128::new(-12345, 5, 3);
+        let rescaled = decimal.rescale(1).unwrap();
+        assert_eq!(-123, rescaled.val());
+        assert_eq!("-12.3", rescaled.to_string());
+
+        // same scale
+        let decimal = Decimal128::new(99999, 5, 2);
+        assert_eq!(decimal, decimal.rescale(2).unwrap());
+
+        // overflow the precision
+        let decimal = Decimal128::new(99999, 5, 2);
+        assert!(decimal.rescale(3).is_err());
+
+        // invalid scale
+        let decimal = Decimal128::new(1, 5, 2);
+        assert!(decimal.rescale(6).is_err());
+    }
+
+    #[test]
+    fn test_decimal128_to_unscaled_str() {
+        let decimal = Decimal128::from_str("1234.56").unwrap();
+        assert_eq!(("123456".to_string(), 2), decimal.to_unscaled_str());
+
+        let decimal = Decimal128::from_str("-0.05").unwrap();
+        assert_eq!(("-5".to_string(), 2), decimal.to_unscaled_str());
+
+        let decimal = Decimal128::new(-123, 3, 0);
+        assert_eq!(("-123".to_string(), 0), decimal.to_unscaled_str());
+
+        let decimal = Decimal128::try_new(123, 3, -2).unwrap();
+        assert_eq!(("123".to_string(), -2), decimal.to_unscaled_str());
+    }
+
+    #[test]
+    fn test_decimal128_to_rust_decimal() {
+        let decimal = Decimal128::new(123456, 10, 2);
+        assert_eq!(
+            Some(RustDecimal::from_str("1234.56").unwrap()),
+            decimal.to_rust_decimal()
+        );
+
+        let decimal = Decimal128::new(-1, 10, 0);
+        assert_eq!(
+            Some(RustDecimal::from_str("-1").unwrap()),
+            decimal.to_rust_decimal()
+        );
+
+        let decimal = Decimal128::new(i128::MAX - 1, 38, 0);
+        assert_eq!(None, decimal.to_rust_decimal());
+    }
+
+    #[test]
+    fn test_decimal128_display_width() {
+        let decimals = [
+            Decimal128::new(123456, 10, 2),
+            Decimal128::new(-123456, 10, 2),
+            Decimal128::new(0, 10, 0),
+            Decimal128::new(-7, 10, 0),
+            Decimal128::new(5, 10, 3),
+            Decimal128::new(-5, 10, 3),
+            Decimal128::new(i128::MAX, 38, 10),
+            Decimal128::new(i128::MIN, 38, 0),
+            Decimal128 {
+                value: 123,
+                precision: 10,
+                scale: -2,
+            },
+            Decimal128 {
+                value: -123,
+                precision: 10,
+                scale: -2,
+            },
+        ];
+        for decimal in decimals {
+            assert_eq!(
+                decimal.to_string().len(),
+                decimal.display_width(),
+                "{decimal}"
+            );
+        }
+        assert_eq!(7, Decimal128::new(123456, 10, 2).display_width());
+        assert_eq!(6, Decimal128::new(-5, 10, 3).display_width());
+    }
+
+    #[test]
+    fn test_decimal128_validated_constructors() {
+        let value = -123456789_i128;
+        let (hi, lo) = Decimal128::new(value, 9, 2).split_value();
+        assert_eq!(
+            Decimal128::try_new(value, 9, 2).unwrap(),
+            Decimal128::try_from_value_precision_scale(hi, lo, 9, 2).unwrap()
+        );
+        assert_eq!(
+            value,
+            Decimal128::try_from_value_precision_scale(hi, lo, 9, 2)
+                .unwrap()
+                .val()
+        );
+
+        for (precision, scale) in [(0, 0), (39, 0), (38, 39), (3, 4)] {
+            assert!(Decimal128::try_new(value, precision, scale).is_err());
+            assert!(Decimal128::try_from_value_precision_scale(hi, lo, precision, scale).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decimal128_le_bytes() {
+        for value in [0, 1, -1, 123456789, -123456789, i128::MAX, i128::MIN] {
+            let decimal = Decimal128::new(value, 38, 2);
+            let bytes = decimal.to_le_bytes();
+            assert_eq!(decimal, Decimal128::from_le_bytes(&bytes, 38, 2));
+        }
+
+        let mut bytes = [0xff; 16];
+        assert_eq!(-1, Decimal128::from_le_bytes(&bytes, 10, 0).val());
+        bytes[0] = 0xfe;
+        assert_eq!(-2, Decimal128::from_le_bytes(&bytes, 10, 0).val());
+        let mut expect = [0; 16];
+        expect[0] = 1;
+        assert_eq!(expect, Decimal128::new(1, 10, 0).to_le_bytes());
+
+        // Same layout as the arrow buffer.
+        let array = arrow::array::Decimal128Array::from(vec![-123456, 789]);
+        let buffer = array.values().inner().as_slice();
+        for (i, value) in [-123456, 789].into_iter().enumerate() {
+            let bytes: &[u8; 16] = buffer[i * 16..(i + 1) * 16].try_into().unwrap();
+            assert_eq!(value, Decimal128::from_le_bytes(bytes, 10, 0).val());
+        }
+    }
+
+    #[test]
+    fn test_decimal128_approx_eq() {
+        let a = Decimal128::new(1000, 4, 3);
+        let b = Decimal128::new(1001, 4, 3);
+        let epsilon = Decimal128::new(1, 3, 2);
+        assert!(a.approx_eq(&b, epsilon));
+        assert!(b.approx_eq(&a, epsilon));
+
+        // The difference equals epsilon.
+        let c = Decimal128::new(101, 3, 2);
+        let d = Decimal128::new(1, 1, 0);
+        assert!(c.approx_eq(&d, epsilon));
+        assert!(!c.approx_eq(&d, Decimal128::new(9, 3, 3)));
+
+        // The sign of epsilon is ignored.
+        assert!(a.approx_eq(&b, Decimal128::new(-1, 3, 2)));
+        assert!(!a.approx_eq(&Decimal128::new(-1000, 4, 3), epsilon));
+
+        // Large values with different scales.
+        let e = Decimal128::new(i128::MAX, 38, 0);
+        let f = Decimal128::new(i128::MAX, 38, 10);
+        assert!(!e.approx_eq(&f, epsilon));
+        assert!(e.approx_eq(&e, Decimal128::new(0, 1, 0)));
+    }
+
+    #[test]
+    fn test_decimal128_to_string_grouped() {
+        let cases = [
+            (123456789, 9, 2, ',', "1,234,567.89"),
+            (-123456789, 9, 2, ',', "-1,234,567.89"),
+            (123456, 6, 0, ' ', "123 456"),
+            (-1234, 5, 0, '_', "-1_234"),
+            (12345, 5, 5, ',', "0.12345"),
+            (999, 3, 0, ',', "999"),
+            (-100000001, 9, 1, ',', "-10,000,000.1"),
+        ];
+        for (value, precision, scale, separator, expect) in cases {
+            let decimal = Decimal128::new(value, precision, scale);
+            assert_eq!(expect, decimal.to_string_grouped(separator));
+        }
+    }
+
+    #[test]
+    fn test_decimal128_display_scale_zero() {
+        let cases = [
+            (0, 1),
+            (7, 1),
+            (-7, 1),
+            (123456789, 9),
+            (-123456789, 10),
+            (i128::MAX, 38),
+            (i128::MIN, 38),
+            // digits beyond the precision are truncated
+            (123456, 3),
+            (-123456, 3),
+        ];
+        for (value, precision) in cases {
+            let decimal = Decimal128::new(value, precision, 0);
+            assert_eq!(
+                format_decimal_str(&value.to_string(), precision as usize, 0),
+                decimal.to_string()
+            );
+        }
+        assert_eq!("123", Decimal128::new(123456, 3, 0).to_string());
+    }
+
+    #[test]
+    fn test_decimal128_display_scale_zero_no_allocation() {
+        let mut buf = String::with_capacity(64);
+        for (value, precision) in [(0, 1), (-7, 1), (123456789, 9), (-123456789, 10)] {
+            buf.clear();
+            let decimal = Decimal128::new(value, precision, 0);
+            assert_eq!(
+                0,
+                count_allocations(|| write!(buf, "{decimal}").unwrap()),
+                "decimal: {decimal:?}"
+            );
+            assert_eq!(value.to_string(), buf);
+        }
+
+        // Decimals with scale are formatted by allocating strings.
+        buf.clear();
+        let decimal = Decimal128::new(12345, 10, 2);
+        assert!(count_allocations(|| write!(buf, "{decimal}").unwrap()) > 0);
+        assert_eq!("123.45", buf);
+    }
 }
@@ -44,6 +44,19 @@ pub enum Error {
 
     #[snafu(display("Invalid precision or scale, resion: {}", reason))]
     InvalidPrecisionOrScale { reason: String, location: Location },
+
+    #[snafu(display(
+        "Decimal value {} overflows precision {} after rescaling to scale {}",
+        value,
+        precision,
+        scale
+    ))]
+    DecimalOverflow {
+        value: i128,
+        precision: u8,
+        scale: i8,
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
@@ -52,6 +65,7 @@ impl ErrorExt for Error {
             Error::BigDecimalOutOfRange { .. } => StatusCode::Internal,
             Error::ParseRustDecimalStr { .. }
             | Error::InvalidPrecisionOrScale { .. }
+            | Error::DecimalOverflow { .. }
             | Error::ParseBigDecimalStr { .. } => StatusCode::InvalidArguments,
         }
     }
@@ -60,6 +74,7 @@ impl ErrorExt for Error {
         match self {
             Error::BigDecimalOutOfRange { location, .. } => Some(*location),
             Error::InvalidPrecisionOrScale { location, .. } => Some(*location),
+            Error::DecimalOverflow { location, .. } => Some(*location),
             Error::ParseRustDecimalStr { .. } | Error::ParseBigDecimalStr { .. } => None,
         }
     }
@@ -150,6 +150,15 @@ impl DateTime {
         Ok(None)
     }
 
+    /// Format DateTime with the offset for given timezone.
+    /// If `tz==None`, the server default timezone will used.
+    /// Returns None if the datetime is out of range.
+    pub fn to_timezone_aware_string(&self, tz: Option<&Timezone>) -> Option<String> {
+        // Safety: the format is valid
+        self.as_formatted_string(DATETIME_FORMAT_WITH_TZ, tz)
+            .unwrap()
+    }
+
     pub fn to_chrono_datetime_with_timezone(&self, tz: Option<&Timezone>) -> Option<NaiveDateTime> {
         let datetime = self.to_chrono_datetime();
         datetime.map(|v| match tz {
@@ -215,6 +224,26 @@ mod tests {
         assert_eq!(time, &dt.to_string());
     }
 
+    #[test]
+    fn test_to_timezone_aware_string() {
+        let dt = DateTime::new(0);
+        assert_eq!(
+            "1970-01-01 00:00:00+0000",
+            dt.to_timezone_aware_string(Some(&Timezone::from_tz_string("UTC").unwrap()))
+                .unwrap()
+        );
+        assert_eq!(
+            "1969-12-31 19:00:00-0500",
+            dt.to_timezone_aware_string(Some(
+                &Timezone::from_tz_string("America/New_York").unwrap()
+            ))
+            .unwrap()
+        );
+        assert!(DateTime::new(i64::MAX)
+            .to_timezone_aware_string(None)
+            .is_none());
+    }
+
     #[test]
     pub fn test_from() {
         let d: DateTime = 42.into();
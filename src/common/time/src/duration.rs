@@ -17,7 +17,9 @@ use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt};
 
+use crate::error::{ArithmeticOverflowSnafu, ParseDurationSnafu, Result};
 use crate::timestamp::TimeUnit;
 
 /// [Duration] represents the elapsed time in either seconds, milliseconds, microseconds or nanoseconds.
@@ -65,6 +67,22 @@ impl Duration {
         }
     }
 
+    /// Return the largest Duration representable in given time unit.
+    pub fn max_value(unit: TimeUnit) -> Self {
+        Self {
+            value: i64::MAX,
+            unit,
+        }
+    }
+
+    /// Return the smallest Duration representable in given time unit.
+    pub fn min_value(unit: TimeUnit) -> Self {
+        Self {
+            value: i64::MIN,
+            unit,
+        }
+    }
+
     /// Return the TimeUnit of current Duration.
     pub fn unit(&self) -> TimeUnit {
         self.unit
@@ -75,6 +93,104 @@ impl Duration {
         self.value
     }
 
+    /// Convert a duration to given time unit.
+    /// Conversion from a duration with smaller unit to a larger unit may cause rounding error.
+    /// Return `None` if conversion causes overflow.
+    pub fn convert_to(&self, unit: TimeUnit) -> Option<Duration> {
+        let value = TimeUnit::scale_value(self.value, self.unit, unit)?;
+        Some(Duration::new(value, unit))
+    }
+
+    /// Multiply the duration by an integer, keeping the unit.
+    /// Return `None` if the result overflows.
+    pub fn checked_mul_int(&self, n: i64) -> Option<Duration> {
+        let value = self.value.checked_mul(n)?;
+        Some(Duration::new(value, self.unit))
+    }
+
+    /// Divide the duration by an integer, keeping the unit, the result is truncated toward zero.
+    /// Return `None` if `n` is zero or the result overflows.
+    pub fn checked_div_int(&self, n: i64) -> Option<Duration> {
+        let value = self.value.checked_div(n)?;
+        Some(Duration::new(value, self.unit))
+    }
+
+    /// Rounds the duration to the nearest whole multiple of `unit` and returns it in `unit`,
+    /// halves are rounded up toward positive infinity, e.g. `1500ms` rounds to `2s` and
+    /// `-1500ms` rounds to `-1s`.
+    ///
+    /// Rounding to a finer unit is exact, the value saturates if it overflows i64.
+    pub fn round_to(&self, unit: TimeUnit) -> Duration {
+        if unit.factor() <= self.unit.factor() {
+            return self.convert_to(unit).unwrap_or_else(|| {
+                if self.value < 0 {
+                    Duration::min_value(unit)
+                } else {
+                    Duration::max_value(unit)
+                }
+            });
+        }
+
+        let div = (unit.factor() / self.unit.factor()) as i128;
+        // Safety: the value is divided by at least 10, so it fits i64.
+        let value = (self.value as i128 + div / 2).div_euclid(div) as i64;
+        Duration::new(value, unit)
+    }
+
+    /// Returns the canonical form of the duration, which is the same for all equal durations.
+    ///
+    /// The duration is converted to the finest unit that can hold its value without
+    /// overflow, e.g. both `1s` and `1000ms` canonicalize to `1000000000ns`.
+    /// Use it before persisting durations as keys.
+    pub fn canonicalize(&self) -> Duration {
+        [
+            TimeUnit::Nanosecond,
+            TimeUnit::Microsecond,
+            TimeUnit::Millisecond,
+        ]
+        .into_iter()
+        // Converting to a coarser unit may lose precision.
+        .filter(|unit| unit.factor() < self.unit.factor())
+        .find_map(|unit| self.convert_to(unit))
+        .unwrap_or(*self)
+    }
+
+    /// Returns the coarsest unit that represents both durations exactly, e.g. `1s` and
+    /// `500ms` share millisecond. Return `None` if no unit can hold both values without
+    /// losing precision or overflow.
+    pub fn common_lossless_unit(&self, other: &Duration) -> Option<TimeUnit> {
+        let exact_in = |duration: &Duration, unit: TimeUnit| {
+            let nanos = duration.value as i128 * duration.unit.factor() as i128;
+            let factor = unit.factor() as i128;
+            nanos % factor == 0 && i64::try_from(nanos / factor).is_ok()
+        };
+        [
+            TimeUnit::Second,
+            TimeUnit::Millisecond,
+            TimeUnit::Microsecond,
+            TimeUnit::Nanosecond,
+        ]
+        .into_iter()
+        .find(|unit| exact_in(self, *unit) && exact_in(other, *unit))
+    }
+
+    /// Convert the duration to the (days, milliseconds) components of an arrow
+    /// `DayTime` interval, both components have the same sign as the duration.
+    /// Return `None` if the duration has sub-millisecond part or the days overflow i32.
+    pub fn to_arrow_interval_day_time(&self) -> Option<(i32, i32)> {
+        const NANOS_PER_MILLI: i128 = 1_000_000;
+        const MILLIS_PER_DAY: i128 = 24 * 60 * 60 * 1000;
+
+        let nanos = self.value as i128 * self.unit.factor() as i128;
+        if nanos % NANOS_PER_MILLI != 0 {
+            return None;
+        }
+        let millis = nanos / NANOS_PER_MILLI;
+        let days = i32::try_from(millis / MILLIS_PER_DAY).ok()?;
+        // Safety: the remainder is less than the milliseconds of a day.
+        Some((days, (millis % MILLIS_PER_DAY) as i32))
+    }
+
     /// Split a [Duration] into seconds part and nanoseconds part.
     /// Notice the seconds part of split result is always rounded down to floor.
     fn split(&self) -> (i64, u32) {
@@ -88,6 +204,235 @@ impl Duration {
         (sec_div, nsec)
     }
 
+    /// Returns the value of the duration in nanosecond as i128, which can hold any
+    /// duration without overflow.
+    pub fn checked_as_nanos(&self) -> Option<i128> {
+        (self.value as i128).checked_mul(self.unit.factor() as i128)
+    }
+
+    /// Returns the value of the duration in nanosecond, or `None` if it overflows i64.
+    pub fn as_nanos_i64(&self) -> Option<i64> {
+        self.checked_as_nanos()
+            .and_then(|nanos| i64::try_from(nanos).ok())
+    }
+
+    /// Restricts the duration to `[min, max]` by the unit-aware ordering, returns
+    /// `min` or `max` as is if the duration is out of the range.
+    ///
+    /// # Panics
+    /// Panics if `min > max`.
+    pub fn clamp(&self, min: Duration, max: Duration) -> Duration {
+        Ord::clamp(*self, min, max)
+    }
+
+    /// Returns the value of the duration in millisecond, values of a finer unit are
+    /// rounded down. Return error if the value overflows i64 in millisecond.
+    ///
+    /// Use it instead of `i64::from` to get a value independent of the unit.
+    pub fn try_as_millis(&self) -> Result<i64> {
+        self.convert_to(TimeUnit::Millisecond)
+            .map(|d| d.value)
+            .with_context(|| ArithmeticOverflowSnafu {
+                msg: format!("duration {} overflows in millisecond", self),
+            })
+    }
+
+    /// Formats the duration in a human-readable form with days, hours, minutes, seconds,
+    /// milliseconds, microseconds and nanoseconds, e.g. `1d2h3m4s` or `1s500ms`.
+    /// Zero components are omitted, so the last component is the finest non-zero one.
+    ///
+    /// The sign is placed before the whole form, e.g. `-90s` is formatted as `-1m30s`.
+    pub fn to_human_readable(&self) -> String {
+        const UNITS: [(u128, &str); 7] = [
+            (86_400_000_000_000, "d"),
+            (3_600_000_000_000, "h"),
+            (60_000_000_000, "m"),
+            (1_000_000_000, "s"),
+            (1_000_000, "ms"),
+            (1_000, "us"),
+            (1, "ns"),
+        ];
+
+        let nanos = self.value as i128 * self.unit.factor() as i128;
+        if nanos == 0 {
+            return "0s".to_string();
+        }
+
+        let mut readable = String::new();
+        if nanos < 0 {
+            readable.push('-');
+        }
+        let mut rest = nanos.unsigned_abs();
+        for (unit_nanos, name) in UNITS {
+            let count = rest / unit_nanos;
+            if count > 0 {
+                readable.push_str(&format!("{count}{name}"));
+            }
+            rest %= unit_nanos;
+        }
+        readable
+    }
+
+    /// Parses an ISO 8601 duration string like `PT1H30M15S` or `P1DT2H`.
+    ///
+    /// Only the fixed-length components (weeks, days, hours, minutes and seconds) are
+    /// supported, years and months are rejected as their lengths are ambiguous. The
+    /// seconds may have a fractional part up to nanosecond precision, and an optional
+    /// leading `-` negates the duration.
+    ///
+    /// The result uses the coarsest unit that represents the duration exactly,
+    /// e.g. `PT1H30M` is parsed as `5400s`.
+    pub fn from_iso8601(s: &str) -> Result<Duration> {
+        let fail = |reason: &str| {
+            ParseDurationSnafu {
+                raw: s,
+                reason: reason.to_string(),
+            }
+            .fail()
+        };
+
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let Some(rest) = rest.strip_prefix('P') else {
+            return fail("missing the leading 'P'");
+        };
+        let (date_part, time_part) = match rest.split_once('T') {
+            Some((date_part, time_part)) => {
+                ensure!(
+                    !time_part.is_empty(),
+                    ParseDurationSnafu {
+                        raw: s,
+                        reason: "empty time portion after 'T'",
+                    }
+                );
+                (date_part, time_part)
+            }
+            None => (rest, ""),
+        };
+        ensure!(
+            !date_part.is_empty() || !time_part.is_empty(),
+            ParseDurationSnafu {
+                raw: s,
+                reason: "no duration component",
+            }
+        );
+
+        const NANOS_PER_SEC: i128 = 1_000_000_000;
+        const DATE_DESIGNATORS: [(char, i128); 2] = [('W', 7 * 86400), ('D', 86400)];
+        const TIME_DESIGNATORS: [(char, i128); 3] = [('H', 3600), ('M', 60), ('S', 1)];
+
+        let mut nanos = 0_i128;
+        for (is_date_part, mut part, designators) in [
+            (true, date_part, &DATE_DESIGNATORS[..]),
+            (false, time_part, &TIME_DESIGNATORS[..]),
+        ] {
+            // Components must follow the order of the designators.
+            let mut next_designator = 0;
+            while !part.is_empty() {
+                let Some(end) = part.find(|c: char| c.is_ascii_alphabetic()) else {
+                    return fail("missing the designator of a component");
+                };
+                let (number, designator) = (&part[..end], part[end..].chars().next().unwrap());
+                part = &part[end + 1..];
+
+                ensure!(
+                    !is_date_part || !matches!(designator, 'Y' | 'M'),
+                    ParseDurationSnafu {
+                        raw: s,
+                        reason: "years and months are not supported",
+                    }
+                );
+                let Some(offset) = designators[next_designator..]
+                    .iter()
+                    .position(|(d, _)| *d == designator)
+                else {
+                    return fail("unexpected or out of order designator");
+                };
+                let secs_per_unit = designators[next_designator + offset].1;
+                next_designator += offset + 1;
+
+                let (int_part, frac_part) = match number.split_once(['.', ',']) {
+                    Some((int_part, frac_part)) if designator == 'S' => (int_part, frac_part),
+                    Some(_) => return fail("only seconds can have a fractional part"),
+                    None => (number, ""),
+                };
+                ensure!(
+                    !int_part.is_empty()
+                        && int_part.bytes().all(|b| b.is_ascii_digit())
+                        && frac_part.len() <= 9
+                        && frac_part.bytes().all(|b| b.is_ascii_digit()),
+                    ParseDurationSnafu {
+                        raw: s,
+                        reason: format!("invalid number {number}"),
+                    }
+                );
+                let Ok(value) = int_part.parse::<i128>() else {
+                    return fail("number overflow");
+                };
+                let frac_nanos = if frac_part.is_empty() {
+                    0
+                } else {
+                    // safety: the fractional part has at most 9 digits
+                    frac_part.parse::<i128>().unwrap() * 10_i128.pow(9 - frac_part.len() as u32)
+                };
+                nanos = value
+                    .checked_mul(secs_per_unit * NANOS_PER_SEC)
+                    .and_then(|v| v.checked_add(frac_nanos))
+                    .and_then(|v| v.checked_add(nanos))
+                    .with_context(|| ParseDurationSnafu {
+                        raw: s,
+                        reason: "duration overflow",
+                    })?;
+            }
+        }
+        if negative {
+            nanos = -nanos;
+        }
+
+        [
+            TimeUnit::Second,
+            TimeUnit::Millisecond,
+            TimeUnit::Microsecond,
+            TimeUnit::Nanosecond,
+        ]
+        .into_iter()
+        .filter(|unit| nanos % unit.factor() as i128 == 0)
+        .find_map(|unit| {
+            i64::try_from(nanos / unit.factor() as i128)
+                .ok()
+                .map(|value| Duration::new(value, unit))
+        })
+        .with_context(|| ParseDurationSnafu {
+            raw: s,
+            reason: "duration overflow",
+        })
+    }
+
+    /// Parses a TTL string like `30d` or `1h 30m` into a duration, the result is
+    /// clamped to `max`.
+    ///
+    /// Return error if the string is not a valid duration or is negative.
+    pub fn parse_ttl(s: &str, max: Duration) -> Result<Duration> {
+        let s = s.trim();
+        ensure!(
+            !s.starts_with('-'),
+            ParseDurationSnafu {
+                raw: s,
+                reason: "TTL can't be negative",
+            }
+        );
+        let ttl = humantime::parse_duration(s).map_err(|e| {
+            ParseDurationSnafu {
+                raw: s,
+                reason: e.to_string(),
+            }
+            .build()
+        })?;
+        Ok(Duration::from(ttl).min(max))
+    }
+
     /// Convert to std::time::Duration.
     pub fn to_std_duration(self) -> std::time::Duration {
         self.into()
@@ -105,7 +450,9 @@ impl From<i64> for Duration {
     }
 }
 
-/// return i64 value of Duration.
+/// Return the raw i64 value of Duration in its own unit, the value is not converted,
+/// so it never overflows, e.g. `1s` returns 1 while `1000ms` returns 1000.
+/// Use [Duration::try_as_millis] to get the value in millisecond with overflow checking.
 impl From<Duration> for i64 {
     fn from(d: Duration) -> Self {
         d.value
@@ -280,6 +627,184 @@ mod tests {
         assert!(d1 == d2);
     }
 
+    #[test]
+    fn test_convert_to() {
+        let d = Duration::new_second(3);
+        assert_eq!(
+            Duration::new_millisecond(3_000),
+            d.convert_to(TimeUnit::Millisecond).unwrap()
+        );
+        assert_eq!(
+            TimeUnit::Nanosecond,
+            d.convert_to(TimeUnit::Nanosecond).unwrap().unit()
+        );
+
+        let d = Duration::new_microsecond(1_500_000);
+        assert_eq!(1, d.convert_to(TimeUnit::Second).unwrap().value());
+
+        // overflow
+        let d = Duration::new_second(i64::MAX / 1_000 + 1);
+        assert!(d.convert_to(TimeUnit::Millisecond).is_none());
+    }
+
+    #[test]
+    fn test_checked_mul_div_int() {
+        // 5m * 3
+        let d = Duration::new_second(300);
+        let res = d.checked_mul_int(3).unwrap();
+        assert_eq!(900, res.value());
+        assert_eq!(TimeUnit::Second, res.unit());
+        assert_eq!(Duration::new_second(-900), d.checked_mul_int(-3).unwrap());
+        assert!(Duration::new_second(i64::MAX).checked_mul_int(2).is_none());
+
+        let res = Duration::new_millisecond(1000).checked_div_int(3).unwrap();
+        assert_eq!(333, res.value());
+        assert_eq!(TimeUnit::Millisecond, res.unit());
+        assert_eq!(
+            Duration::new_millisecond(-333),
+            Duration::new_millisecond(-1000).checked_div_int(3).unwrap()
+        );
+        assert!(d.checked_div_int(0).is_none());
+        assert!(Duration::new_second(i64::MIN).checked_div_int(-1).is_none());
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let canonical = Duration::new_second(1).canonicalize();
+        assert_eq!(TimeUnit::Nanosecond, canonical.unit());
+        assert_eq!(1_000_000_000, canonical.value());
+        let same = Duration::new_millisecond(1000).canonicalize();
+        assert_eq!(
+            (canonical.value(), canonical.unit()),
+            (same.value(), same.unit())
+        );
+
+        // The finest unit that doesn't overflow.
+        let canonical = Duration::new_second(i64::MAX / 1_000_000).canonicalize();
+        assert_eq!(TimeUnit::Microsecond, canonical.unit());
+        let canonical = Duration::new_second(i64::MAX).canonicalize();
+        assert_eq!(TimeUnit::Second, canonical.unit());
+        assert_eq!(i64::MAX, canonical.value());
+
+        let canonical = Duration::new_nanosecond(7).canonicalize();
+        assert_eq!(Duration::new_nanosecond(7), canonical);
+        assert_eq!(TimeUnit::Nanosecond, canonical.unit());
+    }
+
+    #[test]
+    fn test_try_as_millis() {
+        assert_eq!(3000, Duration::new_second(3).try_as_millis().unwrap());
+        assert_eq!(
+            -2,
+            Duration::new_microsecond(-1500).try_as_millis().unwrap()
+        );
+        assert_eq!(
+            i64::MAX / 1_000_000,
+            Duration::new_nanosecond(i64::MAX).try_as_millis().unwrap()
+        );
+        // the raw value is kept by i64::from
+        assert_eq!(i64::MAX, i64::from(Duration::new_second(i64::MAX)));
+
+        let err = Duration::new_second(i64::MAX).try_as_millis().unwrap_err();
+        assert!(err.to_string().contains("overflow"), "{err}");
+        assert!(Duration::new_second(i64::MIN / 100)
+            .try_as_millis()
+            .is_err());
+    }
+
+    #[test]
+    fn test_to_human_readable() {
+        assert_eq!("1m30s", Duration::new_second(90).to_human_readable());
+        assert_eq!("-1m30s", Duration::new_second(-90).to_human_readable());
+        assert_eq!("-5s", Duration::new_second(-5).to_human_readable());
+        assert_eq!(
+            "1d2h3m4s",
+            Duration::new_millisecond(93_784_000).to_human_readable()
+        );
+        assert_eq!("1h1s", Duration::new_second(3601).to_human_readable());
+        assert_eq!("0s", Duration::new_second(0).to_human_readable());
+        assert_eq!(
+            "-106751991167300d15h30m8s",
+            Duration::new_second(i64::MIN).to_human_readable()
+        );
+
+        // sub-second components
+        assert_eq!(
+            "1s500ms",
+            Duration::new_millisecond(1500).to_human_readable()
+        );
+        assert_eq!(
+            "1ms500us",
+            Duration::new_microsecond(1500).to_human_readable()
+        );
+        assert_eq!("-999ns", Duration::new_nanosecond(-999).to_human_readable());
+        assert_eq!(
+            "1m1ms1ns",
+            Duration::new_nanosecond(60_001_000_001).to_human_readable()
+        );
+        assert_eq!(
+            "1d1us",
+            Duration::new_microsecond(86_400_000_001).to_human_readable()
+        );
+        assert_eq!(
+            "-106751d23h47m16s854ms775us808ns",
+            Duration::new_nanosecond(i64::MIN).to_human_readable()
+        );
+    }
+
+    #[test]
+    fn test_common_lossless_unit() {
+        let unit = Duration::new_second(1).common_lossless_unit(&Duration::new_millisecond(500));
+        assert_eq!(Some(TimeUnit::Millisecond), unit);
+        let unit = Duration::new_nanosecond(3_000).common_lossless_unit(&Duration::new_second(-2));
+        assert_eq!(Some(TimeUnit::Microsecond), unit);
+        let unit = Duration::new_millisecond(2000).common_lossless_unit(&Duration::new_second(0));
+        assert_eq!(Some(TimeUnit::Second), unit);
+        // 1ns requires nanosecond but i64::MAX seconds overflows it.
+        let unit =
+            Duration::new_second(i64::MAX).common_lossless_unit(&Duration::new_nanosecond(1));
+        assert_eq!(None, unit);
+    }
+
+    #[test]
+    fn test_to_arrow_interval_day_time() {
+        // 2 days
+        let d = Duration::new_second(2 * 24 * 3600);
+        assert_eq!(Some((2, 0)), d.to_arrow_interval_day_time());
+        let d = Duration::new_millisecond(2 * 24 * 3600 * 1000 + 1500);
+        assert_eq!(Some((2, 1500)), d.to_arrow_interval_day_time());
+        let d = Duration::new_microsecond(-(24 * 3600 * 1_000_000 + 1000));
+        assert_eq!(Some((-1, -1)), d.to_arrow_interval_day_time());
+
+        // sub-millisecond part
+        let d = Duration::new_microsecond(1500);
+        assert_eq!(None, d.to_arrow_interval_day_time());
+        // overflow
+        let d = Duration::new_second(i64::MAX);
+        assert_eq!(None, d.to_arrow_interval_day_time());
+    }
+
+    #[test]
+    fn test_max_min_value() {
+        for unit in [
+            TimeUnit::Second,
+            TimeUnit::Millisecond,
+            TimeUnit::Microsecond,
+            TimeUnit::Nanosecond,
+        ] {
+            let max = Duration::max_value(unit);
+            assert_eq!(i64::MAX, max.value());
+            assert_eq!(unit, max.unit());
+            let min = Duration::min_value(unit);
+            assert_eq!(i64::MIN, min.value());
+            assert_eq!(unit, min.unit());
+        }
+
+        // max value in second can't be converted to a smaller unit
+        let max = Duration::max_value(TimeUnit::Second);
+        assert!(max.convert_to(TimeUnit::Millisecond).is_none());
+    }
+
     #[test]
     fn test_convert_i64() {
         let t = Duration::from(1);
@@ -416,4 +941,189 @@ mod tests {
         let std_duration = std::time::Duration::from(duration);
         assert_eq!(std_duration, std::time::Duration::new(0, 0));
     }
+
+    #[test]
+    fn test_duration_from_iso8601() {
+        assert_eq!(
+            Duration::new_second(5400),
+            Duration::from_iso8601("PT1H30M").unwrap()
+        );
+        assert_eq!(
+            TimeUnit::Second,
+            Duration::from_iso8601("PT1H30M").unwrap().unit()
+        );
+        assert_eq!(
+            Duration::new_second(93600),
+            Duration::from_iso8601("P1DT2H").unwrap()
+        );
+        assert_eq!(
+            Duration::new_second(5415),
+            Duration::from_iso8601("PT1H30M15S").unwrap()
+        );
+        assert_eq!(
+            Duration::new_second(8 * 86400),
+            Duration::from_iso8601("P1W1D").unwrap()
+        );
+        assert_eq!(
+            Duration::new_millisecond(1500),
+            Duration::from_iso8601("PT1.5S").unwrap()
+        );
+        assert_eq!(
+            Duration::new_nanosecond(-1),
+            Duration::from_iso8601("-PT0.000000001S").unwrap()
+        );
+        assert_eq!(
+            Duration::new_second(0),
+            Duration::from_iso8601("PT0S").unwrap()
+        );
+
+        // years and months are ambiguous
+        assert!(Duration::from_iso8601("P1M").is_err());
+        assert!(Duration::from_iso8601("P1Y").is_err());
+        assert!(Duration::from_iso8601("P1Y2DT1H").is_err());
+
+        assert!(Duration::from_iso8601("").is_err());
+        assert!(Duration::from_iso8601("P").is_err());
+        assert!(Duration::from_iso8601("PT").is_err());
+        assert!(Duration::from_iso8601("1H").is_err());
+        assert!(Duration::from_iso8601("PT1").is_err());
+        assert!(Duration::from_iso8601("PT1M1H").is_err());
+        assert!(Duration::from_iso8601("PT1H1H").is_err());
+        assert!(Duration::from_iso8601("PT1.5M").is_err());
+        assert!(Duration::from_iso8601("PT-1S").is_err());
+        assert!(Duration::from_iso8601("PT0.0000000001S").is_err());
+        assert!(Duration::from_iso8601("P99999999999999999999D").is_err());
+    }
+
+    #[test]
+    fn test_duration_parse_ttl() {
+        let max = Duration::max_value(TimeUnit::Second);
+        let ttl = Duration::parse_ttl("30d", max).unwrap();
+        assert_eq!(Duration::new_second(30 * 86400), ttl);
+        let ttl = Duration::parse_ttl(" 1h 30m ", max).unwrap();
+        assert_eq!(Duration::new_second(5400), ttl);
+
+        // negative
+        assert!(Duration::parse_ttl("-30d", max).is_err());
+        // invalid
+        assert!(Duration::parse_ttl("30", max).is_err());
+        assert!(Duration::parse_ttl("", max).is_err());
+        assert!(Duration::parse_ttl("abc", max).is_err());
+
+        // clamped to max
+        let max = Duration::new_second(7 * 86400);
+        let ttl = Duration::parse_ttl("30d", max).unwrap();
+        assert_eq!(max, ttl);
+        let ttl = Duration::parse_ttl("1d", max).unwrap();
+        assert_eq!(Duration::new_second(86400), ttl);
+    }
+
+    #[test]
+    fn test_duration_as_nanos() {
+        let d = Duration::new_second(i64::MAX);
+        assert_eq!(Some(i64::MAX as i128 * 1_000_000_000), d.checked_as_nanos());
+        assert_eq!(None, d.as_nanos_i64());
+
+        let d = Duration::new_millisecond(-1500);
+        assert_eq!(Some(-1_500_000_000), d.checked_as_nanos());
+        assert_eq!(Some(-1_500_000_000), d.as_nanos_i64());
+
+        let d = Duration::new_nanosecond(i64::MIN);
+        assert_eq!(Some(i64::MIN), d.as_nanos_i64());
+        // i64::MAX nanoseconds are about 292 years.
+        assert_eq!(None, Duration::new_second(292 * 366 * 86400).as_nanos_i64());
+    }
+
+    #[test]
+    fn test_duration_clamp() {
+        let d = Duration::new_second(5);
+        let res = d.clamp(Duration::new_second(1), Duration::new_second(3));
+        assert_eq!(Duration::new_second(3), res);
+        assert_eq!(TimeUnit::Second, res.unit());
+        let res = d.clamp(Duration::new_second(10), Duration::new_second(20));
+        assert_eq!(Duration::new_second(10), res);
+        let res = d.clamp(Duration::new_second(1), Duration::new_second(20));
+        assert_eq!(Duration::new_second(5), res);
+
+        // Mixed units.
+        let d = Duration::new_millisecond(5000);
+        let res = d.clamp(Duration::new_second(1), Duration::new_second(3));
+        assert_eq!(Duration::new_second(3), res);
+        assert_eq!(TimeUnit::Second, res.unit());
+        let res = d.clamp(Duration::new_nanosecond(1), Duration::new_second(6));
+        assert_eq!(TimeUnit::Millisecond, res.unit());
+        assert_eq!(5000, res.value());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duration_clamp_invalid_range() {
+        let _ = Duration::new_second(5).clamp(Duration::new_second(3), Duration::new_second(1));
+    }
+
+    #[test]
+    fn test_duration_round_to() {
+        // Round up.
+        let d = Duration::new_millisecond(1500).round_to(TimeUnit::Second);
+        assert_eq!(TimeUnit::Second, d.unit());
+        assert_eq!(2, d.value());
+        assert_eq!(
+            Duration::new_second(2),
+            Duration::new_millisecond(1999).round_to(TimeUnit::Second)
+        );
+        assert_eq!(
+            Duration::new_millisecond(0),
+            Duration::new_nanosecond(-500_000).round_to(TimeUnit::Millisecond)
+        );
+        assert_eq!(
+            Duration::new_second(-1),
+            Duration::new_millisecond(-1500).round_to(TimeUnit::Second)
+        );
+
+        // Round down.
+        assert_eq!(
+            Duration::new_second(1),
+            Duration::new_millisecond(1499).round_to(TimeUnit::Second)
+        );
+        assert_eq!(
+            Duration::new_second(-2),
+            Duration::new_millisecond(-1501).round_to(TimeUnit::Second)
+        );
+        assert_eq!(
+            Duration::new_microsecond(0),
+            Duration::new_nanosecond(499).round_to(TimeUnit::Microsecond)
+        );
+
+        // Exact boundaries.
+        let d = Duration::new_millisecond(3000).round_to(TimeUnit::Second);
+        assert_eq!(TimeUnit::Second, d.unit());
+        assert_eq!(3, d.value());
+        assert_eq!(
+            Duration::new_second(-3),
+            Duration::new_nanosecond(-3_000_000_000).round_to(TimeUnit::Second)
+        );
+        assert_eq!(
+            Duration::new_second(5),
+            Duration::new_second(5).round_to(TimeUnit::Second)
+        );
+        assert_eq!(
+            i64::MAX / 1_000_000_000 + 1,
+            Duration::new_nanosecond(i64::MAX)
+                .round_to(TimeUnit::Second)
+                .value()
+        );
+
+        // Finer units.
+        let d = Duration::new_second(2).round_to(TimeUnit::Millisecond);
+        assert_eq!(TimeUnit::Millisecond, d.unit());
+        assert_eq!(2000, d.value());
+        assert_eq!(
+            Duration::max_value(TimeUnit::Nanosecond),
+            Duration::new_second(i64::MAX).round_to(TimeUnit::Nanosecond)
+        );
+        assert_eq!(
+            Duration::min_value(TimeUnit::Nanosecond),
+            Duration::new_second(i64::MIN).round_to(TimeUnit::Nanosecond)
+        );
+    }
 }
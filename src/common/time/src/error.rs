@@ -41,6 +41,17 @@ pub enum Error {
     #[snafu(display("Failed to parse a string into Interval, raw string: {}", raw))]
     ParseInterval { raw: String, location: Location },
 
+    #[snafu(display(
+        "Failed to parse a string into Duration, raw string: {}, reason: {}",
+        raw,
+        reason
+    ))]
+    ParseDuration {
+        raw: String,
+        reason: String,
+        location: Location,
+    },
+
     #[snafu(display("Current timestamp overflow"))]
     TimestampOverflow {
         #[snafu(source)]
@@ -91,7 +102,9 @@ impl ErrorExt for Error {
             Error::InvalidDateStr { .. } | Error::ArithmeticOverflow { .. } => {
                 StatusCode::InvalidArguments
             }
-            Error::ParseInterval { .. } => StatusCode::InvalidArguments,
+            Error::ParseInterval { .. } | Error::ParseDuration { .. } => {
+                StatusCode::InvalidArguments
+            }
         }
     }
 
@@ -110,7 +123,9 @@ impl ErrorExt for Error {
             | Error::ParseOffsetStr { .. }
             | Error::ParseTimezoneName { .. } => None,
             Error::InvalidDateStr { location, .. } => Some(*location),
-            Error::ParseInterval { location, .. } => Some(*location),
+            Error::ParseInterval { location, .. } | Error::ParseDuration { location, .. } => {
+                Some(*location)
+            }
         }
     }
 }
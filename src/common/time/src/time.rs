@@ -79,14 +79,8 @@ impl Time {
     /// Convert a time to given time unit.
     /// Return `None` if conversion causes overflow.
     pub fn convert_to(&self, unit: TimeUnit) -> Option<Time> {
-        if self.unit().factor() >= unit.factor() {
-            let mul = self.unit().factor() / unit.factor();
-            let value = self.value.checked_mul(mul as i64)?;
-            Some(Time::new(value, unit))
-        } else {
-            let mul = unit.factor() / self.unit().factor();
-            Some(Time::new(self.value.div_euclid(mul as i64), unit))
-        }
+        let value = TimeUnit::scale_value(self.value, self.unit, unit)?;
+        Some(Time::new(value, unit))
     }
 
     /// Split a [Time] into seconds part and nanoseconds part.
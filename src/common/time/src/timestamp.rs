@@ -227,14 +227,8 @@ impl Timestamp {
     /// Conversion from a timestamp with smaller unit to a larger unit may cause rounding error.
     /// Return `None` if conversion causes overflow.
     pub fn convert_to(&self, unit: TimeUnit) -> Option<Timestamp> {
-        if self.unit().factor() >= unit.factor() {
-            let mul = self.unit().factor() / unit.factor();
-            let value = self.value.checked_mul(mul as i64)?;
-            Some(Timestamp::new(value, unit))
-        } else {
-            let mul = unit.factor() / self.unit().factor();
-            Some(Timestamp::new(self.value.div_euclid(mul as i64), unit))
-        }
+        let value = TimeUnit::scale_value(self.value, self.unit, unit)?;
+        Some(Timestamp::new(value, unit))
     }
 
     /// Convert a timestamp to given time unit.
@@ -466,7 +460,7 @@ impl From<Timestamp> for serde_json::Value {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TimeUnit {
     Second,
     #[default]
@@ -521,6 +515,21 @@ impl TimeUnit {
         }
     }
 
+    /// Scales `value` in unit `from` to unit `to`.
+    ///
+    /// Scaling from a larger unit to a smaller unit multiplies the value and returns `None`
+    /// on overflow. Scaling from a smaller unit to a larger unit rounds the value down to
+    /// floor (negative infinity).
+    pub fn scale_value(value: i64, from: TimeUnit, to: TimeUnit) -> Option<i64> {
+        if from.factor() >= to.factor() {
+            let mul = from.factor() / to.factor();
+            value.checked_mul(mul as i64)
+        } else {
+            let div = to.factor() / from.factor();
+            Some(value.div_euclid(div as i64))
+        }
+    }
+
     pub(crate) fn short_name(&self) -> &'static str {
         match self {
             TimeUnit::Second => "s",
@@ -608,6 +617,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_scale_value() {
+        assert_eq!(
+            Some(1_000),
+            TimeUnit::scale_value(1, TimeUnit::Second, TimeUnit::Millisecond)
+        );
+        assert_eq!(
+            Some(1_000_000_000),
+            TimeUnit::scale_value(1, TimeUnit::Second, TimeUnit::Nanosecond)
+        );
+        assert_eq!(
+            Some(-3_000_000),
+            TimeUnit::scale_value(-3, TimeUnit::Millisecond, TimeUnit::Nanosecond)
+        );
+        assert_eq!(
+            Some(42),
+            TimeUnit::scale_value(42, TimeUnit::Microsecond, TimeUnit::Microsecond)
+        );
+        // Round down to floor.
+        assert_eq!(
+            Some(1),
+            TimeUnit::scale_value(1_999, TimeUnit::Millisecond, TimeUnit::Second)
+        );
+        assert_eq!(
+            Some(-2),
+            TimeUnit::scale_value(-1_001, TimeUnit::Millisecond, TimeUnit::Second)
+        );
+        assert_eq!(
+            Some(i64::MAX / 1_000_000_000),
+            TimeUnit::scale_value(i64::MAX, TimeUnit::Nanosecond, TimeUnit::Second)
+        );
+
+        // Overflow.
+        assert_eq!(
+            None,
+            TimeUnit::scale_value(i64::MAX, TimeUnit::Second, TimeUnit::Millisecond)
+        );
+        assert_eq!(
+            None,
+            TimeUnit::scale_value(
+                i64::MIN / 1_000_000_000 - 1,
+                TimeUnit::Second,
+                TimeUnit::Nanosecond
+            )
+        );
+        assert_eq!(
+            None,
+            TimeUnit::scale_value(
+                i64::MAX / 1_000 + 1,
+                TimeUnit::Microsecond,
+                TimeUnit::Nanosecond
+            )
+        );
+    }
+
     #[test]
     pub fn test_timestamp() {
         let t = Timestamp::new(1, TimeUnit::Millisecond);
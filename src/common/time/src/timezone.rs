@@ -57,7 +57,7 @@ pub fn parse_timezone(tz: Option<&str>) -> Timezone {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Timezone {
     Offset(FixedOffset),
     Named(Tz),
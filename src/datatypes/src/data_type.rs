@@ -214,6 +214,14 @@ impl ConcreteDataType {
         matches!(self, ConcreteDataType::Decimal128(_))
     }
 
+    /// Returns true if the type is a nested type, that is a list or dictionary.
+    pub fn is_nested(&self) -> bool {
+        matches!(
+            self,
+            ConcreteDataType::List(_) | ConcreteDataType::Dictionary(_)
+        )
+    }
+
     pub fn numerics() -> Vec<ConcreteDataType> {
         vec![
             ConcreteDataType::int8_datatype(),
@@ -16,7 +16,9 @@ use common_time::duration::Duration;
 use common_time::timestamp::TimeUnit;
 use paste::paste;
 use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
 
+use crate::error::{CastTypeSnafu, Result};
 use crate::prelude::{Scalar, Value, ValueRef};
 use crate::scalars::ScalarRef;
 use crate::types::{
@@ -38,6 +40,15 @@ macro_rules! define_duration_with_unit {
                 pub fn new(val: i64) -> Self {
                     Self(Duration::new(val, TimeUnit::$unit))
                 }
+
+                /// Convert the duration of any unit to this unit, values of a finer unit
+                /// are rounded down. Return error if the conversion overflows.
+                pub fn try_from_duration(d: Duration) -> Result<Self> {
+                    let duration = d.convert_to(TimeUnit::$unit).with_context(|| CastTypeSnafu {
+                        msg: format!("Duration {} overflows in unit {:?}", d, TimeUnit::$unit),
+                    })?;
+                    Ok(Self(duration))
+                }
             }
 
             impl Default for [<Duration $unit>] {
@@ -158,4 +169,22 @@ mod tests {
         let native: i64 = duration.into_native();
         assert_eq!(native, 456);
     }
+
+    #[test]
+    fn test_duration_try_from_duration() {
+        let d = DurationSecond::try_from_duration(Duration::new_nanosecond(12_345_000_000_001))
+            .unwrap();
+        assert_eq!(DurationSecond::new(12_345), d);
+        let d = DurationMillisecond::try_from_duration(Duration::new_second(-3)).unwrap();
+        assert_eq!(DurationMillisecond::new(-3_000), d);
+        let d = DurationMicrosecond::try_from_duration(Duration::new_microsecond(7)).unwrap();
+        assert_eq!(DurationMicrosecond::new(7), d);
+
+        let err =
+            DurationNanosecond::try_from_duration(Duration::new_second(i64::MAX)).unwrap_err();
+        assert!(err.to_string().contains("overflows"), "{err}");
+        assert!(
+            DurationMicrosecond::try_from_duration(Duration::new_millisecond(i64::MIN)).is_err()
+        );
+    }
 }
@@ -117,6 +117,12 @@ pub enum Error {
     #[snafu(display("Invalid timestamp precision: {}", precision))]
     InvalidTimestampPrecision { precision: u64, location: Location },
 
+    #[snafu(display("Invalid duration precision: {}", precision))]
+    InvalidDurationPrecision { precision: u64, location: Location },
+
+    #[snafu(display("Invalid duration type: {}", spec))]
+    InvalidDurationType { spec: String, location: Location },
+
     #[snafu(display("Column {} already exists", column))]
     DuplicateColumn { column: String, location: Location },
 
@@ -139,6 +145,22 @@ pub enum Error {
         error: arrow::error::ArrowError,
         location: Location,
     },
+
+    #[snafu(display("Failed to parse decimal from string: {}", raw))]
+    ParseDecimal {
+        raw: String,
+        #[snafu(source)]
+        error: common_decimal::error::Error,
+        location: Location,
+    },
+
+    #[snafu(display("Failed to rescale decimal to scale {}", scale))]
+    RescaleDecimal {
+        scale: i8,
+        #[snafu(source)]
+        error: common_decimal::error::Error,
+        location: Location,
+    },
 }
 
 impl ErrorExt for Error {
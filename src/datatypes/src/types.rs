@@ -30,14 +30,17 @@ mod timestamp_type;
 
 pub use binary_type::BinaryType;
 pub use boolean_type::BooleanType;
-pub use cast::{cast, cast_with_opt};
+pub use cast::{
+    can_cast_type_lossless, cast, cast_vector_with_opt, cast_vector_with_report, cast_with_opt,
+    date_to_components, is_order_preserving_cast, values_equal_with_cast, CastReport,
+};
 pub use date_type::DateType;
 pub use datetime_type::DateTimeType;
-pub use decimal_type::Decimal128Type;
+pub use decimal_type::{decimal_add, Decimal128Type};
 pub use dictionary_type::DictionaryType;
 pub use duration_type::{
-    DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType, DurationSecondType,
-    DurationType,
+    parse_duration_type, precision_to_time_unit, DurationMicrosecondType, DurationMillisecondType,
+    DurationNanosecondType, DurationSecondType, DurationType,
 };
 pub use interval_type::{
     IntervalDayTimeType, IntervalMonthDayNanoType, IntervalType, IntervalYearMonthType,
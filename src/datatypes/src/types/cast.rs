@@ -12,11 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fmt::Write;
+
+use base64::Engine;
+use chrono::{Datelike, NaiveDateTime};
+use common_time::timestamp::TimeUnit;
+use common_time::timezone::Timezone;
+use common_time::util::datetime_to_utc;
+use common_time::{Date, Interval, Timestamp};
+use snafu::ResultExt;
+
 use crate::data_type::{ConcreteDataType, DataType};
 use crate::error::{self, Error, Result};
-use crate::types::TimeType;
+use crate::types::{IntervalType, TimeType};
 use crate::value::Value;
-use crate::vectors::Helper;
+use crate::vectors::{Helper, Vector, VectorRef};
 
 /// Used to cast the value to dest ConcreteDataType temporarily.
 /// To keep the same behavior as arrow-rs.
@@ -38,6 +48,40 @@ pub struct CastOption {
     /// decide how to handle cast failures,
     /// either return NULL (strict=false) or return ERR (strict=true)
     pub strict: bool,
+    /// the timezone used by casts between timestamps and calendar types,
+    /// use UTC if it's None
+    pub timezone: Option<Timezone>,
+    /// decide whether to treat integer to float casts that can't represent
+    /// the value exactly as cast failures
+    pub reject_precision_loss: bool,
+    /// the unit of the integer value when casting integers to timestamps,
+    /// the result is converted to the unit of the destination type. Millisecond
+    /// by default
+    pub default_timestamp_unit: TimeUnit,
+    /// the chrono format strings tried in order when casting strings to timestamps,
+    /// fallback to the default parser if none of them matches
+    pub timestamp_formats: Vec<String>,
+    /// the chrono format string used to format dates when casting dates to strings,
+    /// use the ISO 8601 format if it's None
+    pub date_format: Option<String>,
+    /// decide whether to reinterpret the bits of integers when casting between signed
+    /// and unsigned integers of the same width, e.g. `Int64(-1)` to `UInt64(u64::MAX)`,
+    /// instead of checking the range of the value. It's dangerous as the value changes.
+    pub reinterpret: bool,
+    /// the encoding of strings when casting strings to binaries
+    pub binary_string_encoding: BinaryEncoding,
+}
+
+/// The encoding of binary data in strings.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryEncoding {
+    /// The UTF-8 bytes of the string are the binary data.
+    #[default]
+    Utf8,
+    /// Hex digits with an optional `\x` prefix, e.g. `\x48656c6c6f`.
+    Hex,
+    /// Standard base64 with padding, e.g. `SGVsbG8=`.
+    Base64,
 }
 
 impl CastOption {
@@ -57,11 +101,20 @@ impl CastOption {
 /// If success, return the casted value.
 /// If CastOption's strict is true, return an error if the cast fails.
 /// If CastOption's strict is false, return NULL if the cast fails.
+/// Casting any value to the null type always returns NULL, even in strict mode.
 pub fn cast_with_opt(
     src_value: Value,
     dest_type: &ConcreteDataType,
     cast_option: &CastOption,
 ) -> Result<Value> {
+    if dest_type.is_null() {
+        return Ok(Value::Null);
+    }
+    if let Value::String(s) = &src_value {
+        if let Some(value) = try_cast_str(s.as_utf8(), dest_type) {
+            return Ok(value);
+        }
+    }
     if !can_cast_type(&src_value, dest_type) {
         if cast_option.strict {
             return Err(invalid_type_cast(&src_value, dest_type));
@@ -69,7 +122,68 @@ pub fn cast_with_opt(
             return Ok(Value::Null);
         }
     }
-    let new_value = dest_type.try_cast(src_value.clone());
+    if cast_option.reject_precision_loss && loses_precision(&src_value, dest_type) {
+        if cast_option.strict {
+            return Err(precision_loss_cast(&src_value, dest_type));
+        } else {
+            return Ok(Value::Null);
+        }
+    }
+    if cast_option.reinterpret {
+        if let Some(value) = reinterpret_integer(&src_value, dest_type) {
+            return Ok(value);
+        }
+    }
+    let new_value = match (&src_value, dest_type) {
+        (Value::Timestamp(ts), ConcreteDataType::Date(_)) => {
+            timestamp_to_date(ts, cast_option.timezone.as_ref())
+        }
+        (Value::Int64(v), ConcreteDataType::Timestamp(t)) => {
+            Timestamp::new(*v, cast_option.default_timestamp_unit)
+                .convert_to(t.unit())
+                .map(Value::Timestamp)
+        }
+        (Value::String(s), ConcreteDataType::Timestamp(t))
+            if !cast_option.timestamp_formats.is_empty() =>
+        {
+            parse_timestamp_with_formats(
+                s.as_utf8(),
+                &cast_option.timestamp_formats,
+                cast_option.timezone.as_ref(),
+            )
+            .and_then(|ts| ts.convert_to(t.unit()))
+            .map(Value::Timestamp)
+            .or_else(|| dest_type.try_cast(src_value.clone()))
+        }
+        (Value::String(s), ConcreteDataType::Binary(_))
+            if cast_option.binary_string_encoding != BinaryEncoding::Utf8 =>
+        {
+            decode_binary_string(s.as_utf8(), cast_option.binary_string_encoding)
+                .map(|bytes| Value::Binary(bytes.into()))
+        }
+        // The integer is the total months of the year-month interval.
+        (Value::Int32(v), ConcreteDataType::Interval(IntervalType::YearMonth(_))) => {
+            Some(Value::Interval(Interval::from_year_month(*v)))
+        }
+        (Value::Int64(v), ConcreteDataType::Interval(IntervalType::YearMonth(_))) => {
+            i32::try_from(*v)
+                .ok()
+                .map(|months| Value::Interval(Interval::from_year_month(months)))
+        }
+        // Fails if the duration has sub-millisecond part or the days overflow i32.
+        (Value::Duration(d), ConcreteDataType::Interval(IntervalType::DayTime(_))) => d
+            .to_arrow_interval_day_time()
+            .map(|(days, millis)| Value::Interval(Interval::from_day_time(days, millis))),
+        (Value::DateTime(dt), ConcreteDataType::String(_)) if cast_option.timezone.is_some() => dt
+            .to_timezone_aware_string(cast_option.timezone.as_ref())
+            .map(|s| Value::String(s.into())),
+        (Value::Date(d), ConcreteDataType::String(_)) if cast_option.date_format.is_some() => {
+            // Safety: the date format is checked above.
+            let format = cast_option.date_format.as_deref().unwrap();
+            format_date(*d, format).map(|s| Value::String(s.into()))
+        }
+        _ => dest_type.try_cast(src_value.clone()),
+    };
     match new_value {
         Some(v) => Ok(v),
         None => {
@@ -82,6 +196,134 @@ pub fn cast_with_opt(
     }
 }
 
+/// Returns true if the two values are equal after casting them to a common type.
+///
+/// Numeric values are compared as Float64 if either of them is a float, otherwise as
+/// UInt64 if both are unsigned, or as Int64. A string is cast to the type of the other
+/// value. Other values are cast to the type of the other value if [can_cast_type] allows.
+///
+/// Values are cast in non-strict mode, NULL values and values failed to cast are never equal.
+pub fn values_equal_with_cast(a: &Value, b: &Value) -> bool {
+    if a.is_null() || b.is_null() {
+        return false;
+    }
+    let (a_type, b_type) = (a.data_type(), b.data_type());
+    if a_type == b_type {
+        return a == b;
+    }
+
+    let common_type = if a_type.is_numeric() && b_type.is_numeric() {
+        if a_type.is_float() || b_type.is_float() {
+            ConcreteDataType::float64_datatype()
+        } else if a_type.is_unsigned() && b_type.is_unsigned() {
+            ConcreteDataType::uint64_datatype()
+        } else {
+            ConcreteDataType::int64_datatype()
+        }
+    } else if a_type.is_string() {
+        b_type
+    } else if b_type.is_string() {
+        a_type
+    } else if can_cast_type(a, &b_type) {
+        b_type
+    } else if can_cast_type(b, &a_type) {
+        a_type
+    } else {
+        return false;
+    };
+
+    let cast_option = CastOption::default();
+    match (
+        cast_with_opt(a.clone(), &common_type, &cast_option),
+        cast_with_opt(b.clone(), &common_type, &cast_option),
+    ) {
+        (Ok(a), Ok(b)) => !a.is_null() && a == b,
+        _ => false,
+    }
+}
+
+/// Cast all values of the vector to dest_type with CastOption by [cast_with_opt].
+///
+/// The destination [MutableVector](crate::vectors::MutableVector) is created with the length
+/// of the src vector as capacity up front, so pushing the casted values never reallocates.
+///
+/// # Returns
+/// If CastOption's strict is true, return an error on the first value that fails to cast.
+/// If CastOption's strict is false, the values failed to cast become NULL.
+pub fn cast_vector_with_opt(
+    src: &dyn Vector,
+    dest_type: &ConcreteDataType,
+    cast_option: &CastOption,
+) -> Result<VectorRef> {
+    cast_vector_with_report(src, dest_type, cast_option, false).map(|(vector, _)| vector)
+}
+
+/// Report of the values failed to cast to NULL by [cast_vector_with_report].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CastReport {
+    /// The number of non-null values that become NULL as they fail to cast.
+    pub failure_count: usize,
+    /// The indices of the failed values in ascending order, only recorded if
+    /// `record_failure_indices` is true.
+    pub failure_indices: Vec<usize>,
+}
+
+/// Cast all values of the vector to dest_type like [cast_vector_with_opt] and report the
+/// values failed to cast. Recording the indices of the failures is optional to avoid
+/// the overhead when callers only need the count.
+pub fn cast_vector_with_report(
+    src: &dyn Vector,
+    dest_type: &ConcreteDataType,
+    cast_option: &CastOption,
+    record_failure_indices: bool,
+) -> Result<(VectorRef, CastReport)> {
+    if let Some(vector) = cast_boolean_vector_to_integer(src, dest_type)? {
+        // Casting booleans to integers never fails.
+        return Ok((vector, CastReport::default()));
+    }
+
+    let mut builder = dest_type.create_mutable_vector(src.len());
+    let mut report = CastReport::default();
+    for i in 0..src.len() {
+        let src_value = src.get(i);
+        let is_null = src_value.is_null();
+        let value = cast_with_opt(src_value, dest_type, cast_option)?;
+        if value.is_null() && !is_null {
+            report.failure_count += 1;
+            if record_failure_indices {
+                report.failure_indices.push(i);
+            }
+        }
+        builder.try_push_value_ref(value.as_value_ref())?;
+    }
+    Ok((builder.to_vector(), report))
+}
+
+/// Cast the boolean vector to the integer dest_type by the arrow cast kernel, which
+/// avoids converting each element to a [Value]. Return None for other casts.
+fn cast_boolean_vector_to_integer(
+    src: &dyn Vector,
+    dest_type: &ConcreteDataType,
+) -> Result<Option<VectorRef>> {
+    let is_integer = matches!(
+        dest_type,
+        ConcreteDataType::Int8(_)
+            | ConcreteDataType::Int16(_)
+            | ConcreteDataType::Int32(_)
+            | ConcreteDataType::Int64(_)
+            | ConcreteDataType::UInt8(_)
+            | ConcreteDataType::UInt16(_)
+            | ConcreteDataType::UInt32(_)
+            | ConcreteDataType::UInt64(_)
+    );
+    if !src.data_type().is_boolean() || !is_integer {
+        return Ok(None);
+    }
+    let array = arrow::compute::cast(&src.to_arrow_array(), &dest_type.as_arrow_type())
+        .context(error::ArrowComputeSnafu)?;
+    Helper::try_into_vector(array).map(Some)
+}
+
 /// Return true if the src_value can be casted to dest_type,
 /// Otherwise, return false.
 /// Notice: this function does not promise that the `cast_with_opt` will succeed,
@@ -113,6 +355,14 @@ pub fn can_cast_type(src_value: &Value, dest_type: &ConcreteDataType) -> bool {
         ) => true,
 
         (String(_), Binary(_)) => true,
+        (String(_), Decimal128(_)) => true,
+        (Float32(_) | Float64(_), Decimal128(_)) => true,
+        (
+            UInt8(_) | UInt16(_) | UInt32(_) | UInt64(_) | Int8(_) | Int16(_) | Int32(_) | Int64(_),
+            Decimal128(_),
+        ) => true,
+        // Rendered with the scale of the decimal, so the trailing zeros are kept.
+        (Decimal128(_), String(_)) => true,
 
         // temporal types cast
         // Date type
@@ -136,15 +386,241 @@ pub fn can_cast_type(src_value: &Value, dest_type: &ConcreteDataType) -> bool {
         (Time(_), Time(_)) => true,
         // TODO(QuenKar): interval type cast
         (Interval(_), String(_)) => true,
+        (Interval(IntervalType::DayTime(_)), Int64(_)) => true,
+        // the total months of the year-month interval
+        (Interval(IntervalType::YearMonth(_)), Int32(_)) => true,
+        (Int32(_) | Int64(_), Interval(IntervalType::YearMonth(_))) => true,
         (Duration(_), String(_)) => true,
+        (Duration(_), Interval(IntervalType::DayTime(_))) => true,
         // other situations return false
         (_, _) => false,
     }
 }
 
+/// Return true if casting any value of src_type to dest_type always succeeds without
+/// losing information, e.g. widening numeric casts like `Int8 -> Int64`.
+///
+/// Narrowing casts like `Int64 -> Int8` are castable according to [can_cast_type], but
+/// whether they succeed depends on the value at runtime, so they are not lossless.
+pub fn can_cast_type_lossless(src_type: &ConcreteDataType, dest_type: &ConcreteDataType) -> bool {
+    use ConcreteDataType::*;
+
+    if src_type == dest_type {
+        return true;
+    }
+
+    match (src_type, dest_type) {
+        (Null(_), _) => true,
+        // every numeric and boolean value has a string representation
+        (_, String(_)) => src_type.is_numeric() || src_type.is_boolean(),
+        (Float32(_), Float64(_)) => true,
+        (_, Float32(_) | Float64(_)) => match integer_width(src_type) {
+            // the integer fits in the mantissa of the float
+            Some((_, bits)) => bits < float_mantissa_bits(dest_type),
+            None => false,
+        },
+        _ => match (integer_width(src_type), integer_width(dest_type)) {
+            (Some((src_signed, src_bits)), Some((dest_signed, dest_bits))) => {
+                match (src_signed, dest_signed) {
+                    (false, true) => src_bits < dest_bits,
+                    (true, false) => false,
+                    _ => src_bits <= dest_bits,
+                }
+            }
+            _ => false,
+        },
+    }
+}
+
+/// Return true if casting from src_type to dest_type preserves the order of values,
+/// i.e. `a < b` implies `cast(a) < cast(b)`, so a range predicate on the casted
+/// values can be rewritten to the original values.
+///
+/// Lossless numeric casts like `Int32 -> Int64` and casts of temporal values to finer
+/// units or to their underlying integers preserve the order. Casts from or to strings
+/// don't, e.g. `9 < 10` but `"9" > "10"`.
+pub fn is_order_preserving_cast(src_type: &ConcreteDataType, dest_type: &ConcreteDataType) -> bool {
+    use ConcreteDataType::*;
+
+    if src_type == dest_type {
+        return true;
+    }
+
+    match (src_type, dest_type) {
+        (Null(_), _) | (_, Null(_)) | (String(_), _) | (_, String(_)) => false,
+        (Date(_), Int32(_)) | (DateTime(_), Int64(_)) | (Timestamp(_), Int64(_)) => true,
+        (Timestamp(src), Timestamp(dest)) => src.unit().factor() >= dest.unit().factor(),
+        _ => can_cast_type_lossless(src_type, dest_type),
+    }
+}
+
+/// Return (is_signed, bits) of the integer data type, None if it isn't an integer.
+fn integer_width(data_type: &ConcreteDataType) -> Option<(bool, u32)> {
+    use ConcreteDataType::*;
+
+    match data_type {
+        Int8(_) => Some((true, 8)),
+        Int16(_) => Some((true, 16)),
+        Int32(_) => Some((true, 32)),
+        Int64(_) => Some((true, 64)),
+        UInt8(_) => Some((false, 8)),
+        UInt16(_) => Some((false, 16)),
+        UInt32(_) => Some((false, 32)),
+        UInt64(_) => Some((false, 64)),
+        _ => None,
+    }
+}
+
+/// Return the mantissa bits of the float data type, including the implicit bit.
+fn float_mantissa_bits(data_type: &ConcreteDataType) -> u32 {
+    match data_type {
+        ConcreteDataType::Float32(_) => f32::MANTISSA_DIGITS,
+        ConcreteDataType::Float64(_) => f64::MANTISSA_DIGITS,
+        _ => 0,
+    }
+}
+
+/// Extract the (year, month, day) components of the date, the month and day start from 1.
+/// Return None if the date is out of the supported calendar range.
+pub fn date_to_components(date: Date) -> Option<(i32, u32, u32)> {
+    date.to_chrono_date()
+        .map(|date| (date.year(), date.month(), date.day()))
+}
+
+/// Cast the timestamp to the calendar date in the timezone, use UTC if the timezone is None.
+/// Return None if the timestamp is out of the date range.
+fn timestamp_to_date(ts: &Timestamp, timezone: Option<&Timezone>) -> Option<Value> {
+    ts.to_chrono_datetime_with_timezone(timezone)
+        .map(|datetime| Value::Date(Date::from(datetime.date())))
+}
+
+/// Parse the string with the formats in order, the parsed datetime is interpreted in the
+/// timezone, use UTC if the timezone is None.
+/// Return None if none of the formats matches.
+fn parse_timestamp_with_formats(
+    s: &str,
+    formats: &[String],
+    timezone: Option<&Timezone>,
+) -> Option<Timestamp> {
+    let s = s.trim();
+    let datetime = formats
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())?;
+    let datetime = match timezone {
+        Some(timezone) => datetime_to_utc(&datetime, timezone).single()?,
+        None => datetime,
+    };
+    Timestamp::from_chrono_datetime(datetime)
+}
+
+/// Decode the binary data from the string in the encoding.
+/// Return None if the string is not valid in the encoding.
+fn decode_binary_string(s: &str, encoding: BinaryEncoding) -> Option<Vec<u8>> {
+    match encoding {
+        BinaryEncoding::Utf8 => Some(s.as_bytes().to_vec()),
+        BinaryEncoding::Hex => {
+            let hex = s.strip_prefix("\\x").unwrap_or(s).as_bytes();
+            if hex.len() % 2 != 0 {
+                return None;
+            }
+            hex.chunks(2)
+                .map(|pair| {
+                    let high = (pair[0] as char).to_digit(16)?;
+                    let low = (pair[1] as char).to_digit(16)?;
+                    Some((high * 16 + low) as u8)
+                })
+                .collect()
+        }
+        BinaryEncoding::Base64 => base64::engine::general_purpose::STANDARD.decode(s).ok(),
+    }
+}
+
+/// Format the date by the chrono format string. Return None if the date is
+/// out of range or the format is invalid.
+fn format_date(date: Date, format: &str) -> Option<String> {
+    let mut formatted = String::new();
+    write!(formatted, "{}", date.to_chrono_date()?.format(format)).ok()?;
+    Some(formatted)
+}
+
+/// Parse the string directly as the integer dest_type. Return None if the dest_type
+/// isn't an integer type or the string isn't a valid integer of that type, so the
+/// caller can fall back to the general cast path.
+fn try_cast_str(s: &str, dest_type: &ConcreteDataType) -> Option<Value> {
+    let value = match dest_type {
+        ConcreteDataType::Int8(_) => Value::Int8(s.parse().ok()?),
+        ConcreteDataType::Int16(_) => Value::Int16(s.parse().ok()?),
+        ConcreteDataType::Int32(_) => Value::Int32(s.parse().ok()?),
+        ConcreteDataType::Int64(_) => Value::Int64(s.parse().ok()?),
+        ConcreteDataType::UInt8(_) => Value::UInt8(s.parse().ok()?),
+        ConcreteDataType::UInt16(_) => Value::UInt16(s.parse().ok()?),
+        ConcreteDataType::UInt32(_) => Value::UInt32(s.parse().ok()?),
+        ConcreteDataType::UInt64(_) => Value::UInt64(s.parse().ok()?),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Reinterpret the bits of the integer value as the integer dest_type of the same width
+/// and different signedness. Return None for other casts.
+fn reinterpret_integer(src_value: &Value, dest_type: &ConcreteDataType) -> Option<Value> {
+    let value = match (src_value, dest_type) {
+        (Value::Int8(v), ConcreteDataType::UInt8(_)) => Value::UInt8(*v as u8),
+        (Value::Int16(v), ConcreteDataType::UInt16(_)) => Value::UInt16(*v as u16),
+        (Value::Int32(v), ConcreteDataType::UInt32(_)) => Value::UInt32(*v as u32),
+        (Value::Int64(v), ConcreteDataType::UInt64(_)) => Value::UInt64(*v as u64),
+        (Value::UInt8(v), ConcreteDataType::Int8(_)) => Value::Int8(*v as i8),
+        (Value::UInt16(v), ConcreteDataType::Int16(_)) => Value::Int16(*v as i16),
+        (Value::UInt32(v), ConcreteDataType::Int32(_)) => Value::Int32(*v as i32),
+        (Value::UInt64(v), ConcreteDataType::Int64(_)) => Value::Int64(*v as i64),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Return true if the integer value can't be represented exactly by the float dest_type.
+fn loses_precision(src_value: &Value, dest_type: &ConcreteDataType) -> bool {
+    let value = match src_value {
+        Value::Int8(v) => *v as i128,
+        Value::Int16(v) => *v as i128,
+        Value::Int32(v) => *v as i128,
+        Value::Int64(v) => *v as i128,
+        Value::UInt8(v) => *v as i128,
+        Value::UInt16(v) => *v as i128,
+        Value::UInt32(v) => *v as i128,
+        Value::UInt64(v) => *v as i128,
+        _ => return false,
+    };
+    match dest_type {
+        ConcreteDataType::Float32(_) => value as f32 as i128 != value,
+        ConcreteDataType::Float64(_) => value as f64 as i128 != value,
+        _ => false,
+    }
+}
+
+fn precision_loss_cast(src_value: &Value, dest_type: &ConcreteDataType) -> Error {
+    error::CastTypeSnafu {
+        msg: format!(
+            "Type {} with value {} can't be cast to the destination type {} without losing precision",
+            src_value.data_type(),
+            src_value,
+            dest_type
+        ),
+    }
+    .build()
+}
+
 fn invalid_type_cast(src_value: &Value, dest_type: &ConcreteDataType) -> Error {
     let src_type = src_value.data_type();
-    if src_type.is_string() {
+    if src_type.is_nested() || dest_type.is_nested() {
+        error::CastTypeSnafu {
+            msg: format!(
+                "Type {} can't be cast to the destination type {}, casting to/from nested types is not supported",
+                src_type, dest_type
+            ),
+        }
+        .build()
+    } else if src_type.is_string() {
         error::CastTypeSnafu {
             msg: format!("Could not parse string '{}' to {}", src_value, dest_type),
         }
@@ -173,12 +649,14 @@ fn invalid_type_cast(src_value: &Value, dest_type: &ConcreteDataType) -> Error {
 #[cfg(test)]
 mod tests {
     use common_base::bytes::StringBytes;
+    use common_decimal::Decimal128;
     use common_time::time::Time;
     use common_time::timezone::set_default_timezone;
-    use common_time::{Date, DateTime, Timestamp};
+    use common_time::{DateTime, Duration};
     use ordered_float::OrderedFloat;
 
     use super::*;
+    use crate::value::ListValue;
 
     macro_rules! test_can_cast {
         ($src_value: expr, $($dest_type: ident),+) => {
@@ -214,7 +692,10 @@ mod tests {
     fn test_cast_with_opt() {
         set_default_timezone(Some("Asia/Shanghai")).unwrap();
         // non-strict mode
-        let cast_option = CastOption { strict: false };
+        let cast_option = CastOption {
+            strict: false,
+            ..Default::default()
+        };
         let src_value = Value::Int8(-1);
         let dest_type = ConcreteDataType::uint8_datatype();
         let res = cast_with_opt(src_value, &dest_type, &cast_option);
@@ -222,7 +703,10 @@ mod tests {
         assert_eq!(res.unwrap(), Value::Null);
 
         // strict mode
-        let cast_option = CastOption { strict: true };
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
         let src_value = Value::Int8(-1);
         let dest_type = ConcreteDataType::uint8_datatype();
         let res = cast_with_opt(src_value, &dest_type, &cast_option);
@@ -313,5 +797,1057 @@ mod tests {
             null_datatype,
             string_datatype
         );
+
+        // interval -> other types
+        test_can_cast!(
+            Value::Interval(Interval::from_day_time(1, 1)),
+            null_datatype,
+            int64_datatype,
+            string_datatype
+        );
+    }
+
+    #[test]
+    fn test_can_cast_type_lossless() {
+        let lossless = [
+            (
+                ConcreteDataType::int8_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+            (
+                ConcreteDataType::uint32_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+            (
+                ConcreteDataType::uint16_datatype(),
+                ConcreteDataType::uint16_datatype(),
+            ),
+            (
+                ConcreteDataType::int16_datatype(),
+                ConcreteDataType::float32_datatype(),
+            ),
+            (
+                ConcreteDataType::int32_datatype(),
+                ConcreteDataType::float64_datatype(),
+            ),
+            (
+                ConcreteDataType::float32_datatype(),
+                ConcreteDataType::float64_datatype(),
+            ),
+            (
+                ConcreteDataType::int64_datatype(),
+                ConcreteDataType::string_datatype(),
+            ),
+            (
+                ConcreteDataType::null_datatype(),
+                ConcreteDataType::date_datatype(),
+            ),
+        ];
+        for (src_type, dest_type) in lossless {
+            assert!(
+                can_cast_type_lossless(&src_type, &dest_type),
+                "{src_type:?} -> {dest_type:?}"
+            );
+        }
+
+        let lossy = [
+            (
+                ConcreteDataType::int64_datatype(),
+                ConcreteDataType::int8_datatype(),
+            ),
+            (
+                ConcreteDataType::int8_datatype(),
+                ConcreteDataType::uint64_datatype(),
+            ),
+            (
+                ConcreteDataType::uint32_datatype(),
+                ConcreteDataType::int32_datatype(),
+            ),
+            (
+                ConcreteDataType::int32_datatype(),
+                ConcreteDataType::float32_datatype(),
+            ),
+            (
+                ConcreteDataType::int64_datatype(),
+                ConcreteDataType::float64_datatype(),
+            ),
+            (
+                ConcreteDataType::float64_datatype(),
+                ConcreteDataType::float32_datatype(),
+            ),
+            (
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+        ];
+        for (src_type, dest_type) in lossy {
+            assert!(
+                !can_cast_type_lossless(&src_type, &dest_type),
+                "{src_type:?} -> {dest_type:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_order_preserving_cast() {
+        let preserving = [
+            (
+                ConcreteDataType::int32_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+            (
+                ConcreteDataType::uint8_datatype(),
+                ConcreteDataType::int16_datatype(),
+            ),
+            (
+                ConcreteDataType::float32_datatype(),
+                ConcreteDataType::float64_datatype(),
+            ),
+            (
+                ConcreteDataType::int16_datatype(),
+                ConcreteDataType::float32_datatype(),
+            ),
+            (
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::string_datatype(),
+            ),
+            (
+                ConcreteDataType::timestamp_second_datatype(),
+                ConcreteDataType::timestamp_millisecond_datatype(),
+            ),
+            (
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+            (
+                ConcreteDataType::date_datatype(),
+                ConcreteDataType::int32_datatype(),
+            ),
+        ];
+        for (src_type, dest_type) in preserving {
+            assert!(
+                is_order_preserving_cast(&src_type, &dest_type),
+                "{src_type:?} -> {dest_type:?}"
+            );
+        }
+
+        let not_preserving = [
+            (
+                ConcreteDataType::float64_datatype(),
+                ConcreteDataType::string_datatype(),
+            ),
+            (
+                ConcreteDataType::int64_datatype(),
+                ConcreteDataType::string_datatype(),
+            ),
+            (
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+            (
+                ConcreteDataType::int64_datatype(),
+                ConcreteDataType::int32_datatype(),
+            ),
+            (
+                ConcreteDataType::int8_datatype(),
+                ConcreteDataType::uint8_datatype(),
+            ),
+            (
+                ConcreteDataType::float64_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+            (
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                ConcreteDataType::timestamp_second_datatype(),
+            ),
+            (
+                ConcreteDataType::null_datatype(),
+                ConcreteDataType::int64_datatype(),
+            ),
+        ];
+        for (src_type, dest_type) in not_preserving {
+            assert!(
+                !is_order_preserving_cast(&src_type, &dest_type),
+                "{src_type:?} -> {dest_type:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_interval_day_time_to_int64() {
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        // 2 days and 3000 milliseconds
+        let src_value = Value::Interval(Interval::from_day_time(2, 3000));
+        let res =
+            cast_with_opt(src_value, &ConcreteDataType::int64_datatype(), &cast_option).unwrap();
+        assert_eq!(res, Value::Int64(2 * 86_400_000 + 3000));
+
+        let src_value = Value::Interval(Interval::from_day_time(-1, -500));
+        let res =
+            cast_with_opt(src_value, &ConcreteDataType::int64_datatype(), &cast_option).unwrap();
+        assert_eq!(res, Value::Int64(-86_400_500));
+    }
+
+    #[test]
+    fn test_cast_interval_year_month_int32() {
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let interval_type = ConcreteDataType::interval_year_month_datatype();
+
+        // 2 years and 3 months
+        let interval = Value::Interval(Interval::from_year_month(2 * 12 + 3));
+        let res = cast_with_opt(
+            interval.clone(),
+            &ConcreteDataType::int32_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(res, Value::Int32(27));
+        let res = cast_with_opt(res, &interval_type, &cast_option).unwrap();
+        assert_eq!(res, interval);
+
+        let res = cast_with_opt(Value::Int64(-14), &interval_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Interval(Interval::from_year_month(-14)));
+
+        // overflow i32
+        let src_value = Value::Int64(i32::MAX as i64 + 1);
+        assert!(cast_with_opt(src_value.clone(), &interval_type, &cast_option).is_err());
+        let res = cast_with_opt(src_value, &interval_type, &CastOption::default()).unwrap();
+        assert_eq!(res, Value::Null);
+    }
+
+    #[test]
+    fn test_cast_duration_to_interval_day_time() {
+        let dest_type = ConcreteDataType::interval_day_time_datatype();
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+
+        // 3 days
+        let src_value = Value::Duration(Duration::new_second(3 * 86400));
+        assert!(can_cast_type(&src_value, &dest_type));
+        let res = cast_with_opt(src_value, &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Interval(Interval::from_day_time(3, 0)));
+
+        let src_value = Value::Duration(Duration::new_millisecond(-(86_400_000 + 500)));
+        let res = cast_with_opt(src_value, &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Interval(Interval::from_day_time(-1, -500)));
+
+        // days overflow i32
+        let src_value = Value::Duration(Duration::new_second(i64::MAX));
+        assert!(cast_with_opt(src_value.clone(), &dest_type, &cast_option).is_err());
+        let res = cast_with_opt(src_value, &dest_type, &CastOption::default()).unwrap();
+        assert_eq!(res, Value::Null);
+
+        // sub-millisecond part
+        let src_value = Value::Duration(Duration::new_microsecond(1));
+        assert!(cast_with_opt(src_value, &dest_type, &cast_option).is_err());
+    }
+
+    #[test]
+    fn test_cast_timestamp_to_date_with_timezone() {
+        // 2024-01-01 20:00:00 UTC
+        let src_value = Value::Timestamp(Timestamp::new_second(1704139200));
+        let dest_type = ConcreteDataType::date_datatype();
+
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value.clone(), &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Date(Date::from_str_utc("2024-01-01").unwrap()));
+
+        let cast_option = CastOption {
+            strict: true,
+            timezone: Some(Timezone::from_tz_string("UTC").unwrap()),
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value.clone(), &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Date(Date::from_str_utc("2024-01-01").unwrap()));
+
+        // It's already 2024-01-02 04:00:00 in Asia/Shanghai.
+        let cast_option = CastOption {
+            strict: true,
+            timezone: Some(Timezone::from_tz_string("Asia/Shanghai").unwrap()),
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value, &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Date(Date::from_str_utc("2024-01-02").unwrap()));
+
+        // out of range
+        let src_value = Value::Timestamp(Timestamp::new_second(i64::MAX));
+        assert!(cast_with_opt(src_value.clone(), &dest_type, &cast_option).is_err());
+        let cast_option = CastOption {
+            strict: false,
+            ..cast_option
+        };
+        assert_eq!(
+            cast_with_opt(src_value, &dest_type, &cast_option).unwrap(),
+            Value::Null
+        );
+    }
+
+    #[test]
+    fn test_cast_reject_precision_loss() {
+        let src_value = Value::Int64(16_777_217);
+        let dest_type = ConcreteDataType::float32_datatype();
+
+        // precision loss is allowed by default
+        let cast_option = CastOption::default();
+        let res = cast_with_opt(src_value.clone(), &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Float32(OrderedFloat(16_777_216.0)));
+
+        let cast_option = CastOption {
+            strict: true,
+            reject_precision_loss: true,
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value.clone(), &dest_type, &cast_option);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "Type Int64 with value 16777217 can't be cast to the destination type Float32 without losing precision"
+        );
+        // f64 can represent it exactly
+        let res = cast_with_opt(
+            src_value.clone(),
+            &ConcreteDataType::float64_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(res, Value::Float64(OrderedFloat(16_777_217.0)));
+        // exactly representable
+        let res = cast_with_opt(Value::Int64(16_777_216), &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Float32(OrderedFloat(16_777_216.0)));
+        let res = cast_with_opt(
+            Value::Int64(i64::MAX),
+            &ConcreteDataType::float64_datatype(),
+            &cast_option,
+        );
+        assert!(res.is_err());
+
+        let cast_option = CastOption {
+            strict: false,
+            reject_precision_loss: true,
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value, &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Null);
+    }
+
+    #[test]
+    fn test_cast_vector_with_opt() {
+        use arrow::array::{Array, Int64Array};
+
+        use crate::vectors::{Int32Vector, Int64Vector};
+
+        let num_rows = 10_000;
+        let src = Int32Vector::from_values(0..num_rows as i32);
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let dest_type = ConcreteDataType::int64_datatype();
+        let vector = cast_vector_with_opt(&src, &dest_type, &cast_option).unwrap();
+        assert_eq!(num_rows, vector.len());
+        assert_eq!(dest_type, vector.data_type());
+        let expect = Int64Vector::from_values(0..num_rows as i64);
+        assert_eq!(
+            &expect,
+            vector.as_any().downcast_ref::<Int64Vector>().unwrap()
+        );
+
+        // The values buffer is never reallocated beyond the initial capacity.
+        let array = vector.to_arrow_array();
+        let array = array.as_any().downcast_ref::<Int64Array>().unwrap();
+        let initial_capacity =
+            arrow::util::bit_util::round_upto_multiple_of_64(num_rows * std::mem::size_of::<i64>());
+        assert_eq!(initial_capacity, array.values().inner().capacity());
+
+        // non-strict mode
+        let src = Int64Vector::from(vec![Some(1), None, Some(-1), Some(300)]);
+        let cast_option = CastOption::default();
+        let vector =
+            cast_vector_with_opt(&src, &ConcreteDataType::uint8_datatype(), &cast_option).unwrap();
+        assert_eq!(
+            vec![Value::UInt8(1), Value::Null, Value::Null, Value::Null],
+            (0..vector.len()).map(|i| vector.get(i)).collect::<Vec<_>>()
+        );
+
+        // strict mode
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(
+            cast_vector_with_opt(&src, &ConcreteDataType::uint8_datatype(), &cast_option).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cast_string_to_decimal128() {
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let dest_type = ConcreteDataType::decimal128_datatype(10, 2);
+
+        let cast = |s: &str| cast_with_opt(Value::String(s.into()), &dest_type, &cast_option);
+
+        // parse and rescale
+        let res = cast("12.5").unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(1250, 10, 2)));
+        assert_eq!("12.50", res.to_string());
+        let res = cast("-3").unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(-300, 10, 2)));
+        // extra digits are truncated
+        let res = cast("1.239").unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(123, 10, 2)));
+        let res = cast("99999999.99").unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(9999999999, 10, 2)));
+
+        // precision overflow
+        assert!(cast("100000000").is_err());
+        // not a decimal
+        assert!(cast("abc").is_err());
+
+        let cast_option = CastOption::default();
+        let res =
+            cast_with_opt(Value::String("100000000".into()), &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::Null);
+    }
+
+    #[test]
+    fn test_cast_int_to_timestamp_with_unit() {
+        let dest_type = ConcreteDataType::timestamp_millisecond_datatype();
+        let cast = |unit| {
+            let cast_option = CastOption {
+                strict: true,
+                default_timestamp_unit: unit,
+                ..Default::default()
+            };
+            cast_with_opt(Value::Int64(1000), &dest_type, &cast_option).unwrap()
+        };
+
+        let res = cast(TimeUnit::Millisecond);
+        assert_eq!(res, Value::Timestamp(Timestamp::new_millisecond(1000)));
+        let res = cast(TimeUnit::Second);
+        assert_eq!(res, Value::Timestamp(Timestamp::new_millisecond(1_000_000)));
+        assert_ne!(cast(TimeUnit::Second), cast(TimeUnit::Millisecond));
+        let res = cast(TimeUnit::Microsecond);
+        assert_eq!(res, Value::Timestamp(Timestamp::new_millisecond(1)));
+
+        // overflow
+        let cast_option = CastOption {
+            strict: true,
+            default_timestamp_unit: TimeUnit::Second,
+            ..Default::default()
+        };
+        assert!(cast_with_opt(Value::Int64(i64::MAX), &dest_type, &cast_option).is_err());
+    }
+
+    #[test]
+    fn test_cast_int_to_timestamp_default_unit() {
+        assert_eq!(
+            TimeUnit::Millisecond,
+            CastOption::default().default_timestamp_unit
+        );
+        // The integer is in millisecond with default options.
+        for (dest_type, expect) in [
+            (
+                ConcreteDataType::timestamp_second_datatype(),
+                Timestamp::new_second(1),
+            ),
+            (
+                ConcreteDataType::timestamp_millisecond_datatype(),
+                Timestamp::new_millisecond(1500),
+            ),
+            (
+                ConcreteDataType::timestamp_microsecond_datatype(),
+                Timestamp::new_microsecond(1_500_000),
+            ),
+            (
+                ConcreteDataType::timestamp_nanosecond_datatype(),
+                Timestamp::new_nanosecond(1_500_000_000),
+            ),
+        ] {
+            let res =
+                cast_with_opt(Value::Int64(1500), &dest_type, &CastOption::default()).unwrap();
+            assert_eq!(Value::Timestamp(expect), res, "dest_type: {dest_type}");
+        }
+        // Overflow in non-strict mode.
+        let res = cast_with_opt(
+            Value::Int64(i64::MAX),
+            &ConcreteDataType::timestamp_nanosecond_datatype(),
+            &CastOption::default(),
+        )
+        .unwrap();
+        assert_eq!(Value::Null, res);
+    }
+
+    #[test]
+    fn test_cast_string_to_timestamp_with_formats() {
+        let dest_type = ConcreteDataType::timestamp_millisecond_datatype();
+        let cast_option = CastOption {
+            strict: true,
+            timestamp_formats: vec!["%d.%m.%Y %H:%M".to_string(), "%Y/%m/%d %H:%M".to_string()],
+            ..Default::default()
+        };
+        let res = cast_with_opt(
+            Value::String("2021/01/01 00:00".into()),
+            &dest_type,
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Value::Timestamp(Timestamp::new_millisecond(1609459200000))
+        );
+
+        // parsed in the given timezone
+        let cast_option = CastOption {
+            timezone: Some(Timezone::from_tz_string("+08:00").unwrap()),
+            ..cast_option
+        };
+        let res = cast_with_opt(
+            Value::String("2021/01/01 08:00".into()),
+            &dest_type,
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Value::Timestamp(Timestamp::new_millisecond(1609459200000))
+        );
+
+        // fallback to the default parser
+        let res = cast_with_opt(
+            Value::String("2021-01-01 00:00:00Z".into()),
+            &dest_type,
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(
+            res,
+            Value::Timestamp(Timestamp::new_millisecond(1609459200000))
+        );
+
+        assert!(
+            cast_with_opt(Value::String("2021_01_01".into()), &dest_type, &cast_option).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cast_string_to_binary_with_encoding() {
+        let dest_type = ConcreteDataType::binary_datatype();
+        let cast = |s: &str, encoding| {
+            let cast_option = CastOption {
+                strict: true,
+                binary_string_encoding: encoding,
+                ..Default::default()
+            };
+            cast_with_opt(Value::String(s.into()), &dest_type, &cast_option)
+        };
+        let hello = Value::Binary(b"Hello".to_vec().into());
+
+        assert_eq!(hello, cast("Hello", BinaryEncoding::Utf8).unwrap());
+        assert_eq!(hello, cast("\\x48656c6c6f", BinaryEncoding::Hex).unwrap());
+        assert_eq!(hello, cast("48656C6C6F", BinaryEncoding::Hex).unwrap());
+        assert_eq!(hello, cast("SGVsbG8=", BinaryEncoding::Base64).unwrap());
+        assert_eq!(
+            Value::Binary(vec![].into()),
+            cast("", BinaryEncoding::Hex).unwrap()
+        );
+
+        // invalid encodings
+        assert!(cast("\\x4865f", BinaryEncoding::Hex).is_err());
+        assert!(cast("Hello", BinaryEncoding::Hex).is_err());
+        assert!(cast("SGVsbG8", BinaryEncoding::Base64).is_err());
+        let cast_option = CastOption {
+            binary_string_encoding: BinaryEncoding::Hex,
+            ..Default::default()
+        };
+        let res = cast_with_opt(Value::String("xyz".into()), &dest_type, &cast_option).unwrap();
+        assert_eq!(Value::Null, res);
+    }
+
+    #[test]
+    fn test_cast_reinterpret() {
+        let cast_option = CastOption {
+            strict: true,
+            reinterpret: true,
+            ..Default::default()
+        };
+        let res = cast_with_opt(
+            Value::Int64(-1),
+            &ConcreteDataType::uint64_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(res, Value::UInt64(u64::MAX));
+        let res = cast_with_opt(
+            Value::UInt8(200),
+            &ConcreteDataType::int8_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(res, Value::Int8(-56));
+        let res = cast_with_opt(
+            Value::UInt32(u32::MAX),
+            &ConcreteDataType::int32_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(res, Value::Int32(-1));
+
+        // other casts are unchanged
+        let res = cast_with_opt(
+            Value::Int32(-1),
+            &ConcreteDataType::int64_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(res, Value::Int64(-1));
+        assert!(cast_with_opt(
+            Value::Int32(-1),
+            &ConcreteDataType::uint64_datatype(),
+            &cast_option
+        )
+        .is_err());
+
+        // range checked by default
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        assert!(cast_with_opt(
+            Value::Int64(-1),
+            &ConcreteDataType::uint64_datatype(),
+            &cast_option
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_cast_time_unit() {
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+
+        let res = cast_with_opt(
+            Value::Time(Time::new_second(10)),
+            &ConcreteDataType::time_millisecond_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        let Value::Time(time) = res else {
+            panic!("unexpected value {res:?}");
+        };
+        assert_eq!(10_000, time.value());
+        assert_eq!(&TimeUnit::Millisecond, time.unit());
+
+        // overflow
+        let src_value = Value::Time(Time::new_second(i64::MAX / 1000));
+        let dest_type = ConcreteDataType::time_nanosecond_datatype();
+        assert!(cast_with_opt(src_value.clone(), &dest_type, &cast_option).is_err());
+        let res = cast_with_opt(src_value, &dest_type, &CastOption::default()).unwrap();
+        assert_eq!(Value::Null, res);
+    }
+
+    #[test]
+    fn test_values_equal_with_cast() {
+        // int vs float
+        assert!(values_equal_with_cast(
+            &Value::Int64(1),
+            &Value::Float64(OrderedFloat(1.0))
+        ));
+        assert!(!values_equal_with_cast(
+            &Value::Int64(1),
+            &Value::Float64(OrderedFloat(1.5))
+        ));
+        assert!(values_equal_with_cast(&Value::UInt8(7), &Value::Int32(7)));
+        assert!(!values_equal_with_cast(
+            &Value::UInt64(u64::MAX),
+            &Value::Int64(-1)
+        ));
+
+        // string vs int
+        assert!(values_equal_with_cast(
+            &Value::String("42".into()),
+            &Value::Int32(42)
+        ));
+        assert!(values_equal_with_cast(
+            &Value::Int32(42),
+            &Value::String("42".into())
+        ));
+        assert!(!values_equal_with_cast(
+            &Value::String("abc".into()),
+            &Value::Int32(42)
+        ));
+
+        // same type
+        assert!(values_equal_with_cast(&Value::Int32(3), &Value::Int32(3)));
+        assert!(!values_equal_with_cast(&Value::Int32(3), &Value::Int32(4)));
+
+        // incomparable pairs
+        assert!(!values_equal_with_cast(
+            &Value::Boolean(true),
+            &Value::Date(Date::new(1))
+        ));
+        assert!(!values_equal_with_cast(&Value::Null, &Value::Null));
+        assert!(!values_equal_with_cast(&Value::Int32(0), &Value::Null));
+    }
+
+    #[test]
+    fn test_cast_nested_type_error() {
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let list_type = ConcreteDataType::list_datatype(ConcreteDataType::int32_datatype());
+        let list = Value::List(ListValue::new(
+            Some(Box::new(vec![Value::Int32(1)])),
+            ConcreteDataType::int32_datatype(),
+        ));
+
+        let err = cast_with_opt(list, &ConcreteDataType::int32_datatype(), &cast_option)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("casting to/from nested types is not supported"),
+            "{err}"
+        );
+        let err = cast_with_opt(Value::Int32(1), &list_type, &cast_option)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("casting to/from nested types is not supported"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_cast_integer_to_decimal128() {
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let dest_type = ConcreteDataType::decimal128_datatype(10, 2);
+
+        let cast = |v: Value| cast_with_opt(v, &dest_type, &cast_option);
+
+        // null stays null
+        assert_eq!(cast(Value::Null).unwrap(), Value::Null);
+        // zero isn't null
+        let res = cast(Value::Int64(0)).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(0, 10, 2)));
+        assert_eq!("0.00", res.to_string());
+
+        let res = cast(Value::Int32(-12)).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(-1200, 10, 2)));
+        let res = cast(Value::UInt8(255)).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(25500, 10, 2)));
+
+        // precision overflow
+        assert!(cast(Value::Int64(100_000_000)).is_err());
+        assert!(cast(Value::UInt64(u64::MAX)).is_err());
+
+        // nulls in a vector are propagated instead of converted to zero
+        let vector = crate::vectors::Int64Vector::from(vec![Some(1), None, Some(0)]);
+        let res = cast_vector_with_opt(&vector, &dest_type, &cast_option).unwrap();
+        assert_eq!(Value::Decimal128(Decimal128::new(100, 10, 2)), res.get(0));
+        assert_eq!(Value::Null, res.get(1));
+        assert_eq!(Value::Decimal128(Decimal128::new(0, 10, 2)), res.get(2));
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn test_cast_float_to_decimal128() {
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let dest_type = ConcreteDataType::decimal128_datatype(10, 2);
+
+        let cast = |v: Value| cast_with_opt(v, &dest_type, &cast_option);
+
+        let res = cast(Value::Float64(OrderedFloat(3.14159))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(314, 10, 2)));
+        assert_eq!("3.14", res.to_string());
+        // round half-up
+        let res = cast(Value::Float64(OrderedFloat(2.675))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(268, 10, 2)));
+        let res = cast(Value::Float64(OrderedFloat(-2.675))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(-268, 10, 2)));
+        let res = cast(Value::Float32(OrderedFloat(1.5))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(150, 10, 2)));
+        // f32 is rounded from its own shortest representation
+        let res = cast(Value::Float32(OrderedFloat(2.675))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(268, 10, 2)));
+        let res = cast(Value::Float32(OrderedFloat(0.1))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(10, 10, 2)));
+
+        // tiny floats round to zero
+        for v in [1e-40, -1e-40, 0.004999, f64::MIN_POSITIVE] {
+            let res = cast(Value::Float64(OrderedFloat(v))).unwrap();
+            assert_eq!(res, Value::Decimal128(Decimal128::new(0, 10, 2)));
+        }
+        let res = cast(Value::Float32(OrderedFloat(f32::MIN_POSITIVE))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(0, 10, 2)));
+        let res = cast(Value::Float64(OrderedFloat(0.005))).unwrap();
+        assert_eq!(res, Value::Decimal128(Decimal128::new(1, 10, 2)));
+
+        // precision overflow
+        assert!(cast(Value::Float64(OrderedFloat(1e8))).is_err());
+        // NaN and infinity
+        assert!(cast(Value::Float64(OrderedFloat(f64::NAN))).is_err());
+        assert!(cast(Value::Float64(OrderedFloat(f64::INFINITY))).is_err());
+        let res = cast_with_opt(
+            Value::Float64(OrderedFloat(f64::NAN)),
+            &dest_type,
+            &CastOption::default(),
+        )
+        .unwrap();
+        assert_eq!(res, Value::Null);
+    }
+
+    #[test]
+    fn test_cast_datetime_to_string_with_timezone() {
+        // 2024-01-01 12:00:00 UTC
+        let src_value = Value::DateTime(DateTime::new(1704110400000));
+        let dest_type = ConcreteDataType::string_datatype();
+
+        let cast_option = CastOption {
+            strict: true,
+            timezone: Some(Timezone::from_tz_string("UTC").unwrap()),
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value.clone(), &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::String("2024-01-01 12:00:00+0000".into()));
+
+        let cast_option = CastOption {
+            strict: true,
+            timezone: Some(Timezone::from_tz_string("America/New_York").unwrap()),
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value, &dest_type, &cast_option).unwrap();
+        assert_eq!(res, Value::String("2024-01-01 07:00:00-0500".into()));
+    }
+
+    #[test]
+    fn test_cast_str_to_integer_fast_path() {
+        let dest_types = [
+            ConcreteDataType::int8_datatype(),
+            ConcreteDataType::int16_datatype(),
+            ConcreteDataType::int32_datatype(),
+            ConcreteDataType::int64_datatype(),
+            ConcreteDataType::uint8_datatype(),
+            ConcreteDataType::uint16_datatype(),
+            ConcreteDataType::uint32_datatype(),
+            ConcreteDataType::uint64_datatype(),
+        ];
+        let inputs = [
+            "0",
+            "123",
+            "-5",
+            "+7",
+            " 7",
+            "1.5",
+            "abc",
+            "",
+            "300",
+            "-129",
+            "99999999999999999999",
+        ];
+        let cast_option = CastOption::default();
+        for dest_type in &dest_types {
+            for input in inputs {
+                let src_value = Value::String(input.into());
+                let expected = dest_type.try_cast(src_value.clone()).unwrap_or(Value::Null);
+                let res = cast_with_opt(src_value, dest_type, &cast_option).unwrap();
+                assert_eq!(expected, res, "input: {input:?}, dest_type: {dest_type}");
+            }
+        }
+
+        // Non-integer dest types still use the general path.
+        let res = cast_with_opt(
+            Value::String("1.5".into()),
+            &ConcreteDataType::float64_datatype(),
+            &cast_option,
+        )
+        .unwrap();
+        assert_eq!(Value::Float64(1.5.into()), res);
+    }
+
+    #[test]
+    fn test_cast_date_to_string_with_format() {
+        let src_value = Value::Date(Date::from_str_utc("2021-01-02").unwrap());
+        let dest_type = ConcreteDataType::string_datatype();
+
+        let res = cast_with_opt(src_value.clone(), &dest_type, &CastOption::default()).unwrap();
+        assert_eq!(Value::String("2021-01-02".into()), res);
+
+        let cast_option = CastOption {
+            strict: true,
+            date_format: Some("%m/%d/%Y".to_string()),
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value.clone(), &dest_type, &cast_option).unwrap();
+        assert_eq!(Value::String("01/02/2021".into()), res);
+
+        // The timestamp formats don't affect dates.
+        let cast_option = CastOption {
+            strict: true,
+            timestamp_formats: vec!["%m/%d/%Y".to_string()],
+            ..Default::default()
+        };
+        let res = cast_with_opt(src_value.clone(), &dest_type, &cast_option).unwrap();
+        assert_eq!(Value::String("2021-01-02".into()), res);
+
+        // Invalid format.
+        let cast_option = CastOption {
+            strict: true,
+            date_format: Some("%Q".to_string()),
+            ..Default::default()
+        };
+        assert!(cast_with_opt(src_value, &dest_type, &cast_option).is_err());
+    }
+
+    #[test]
+    fn test_cast_vector_with_report() {
+        use crate::vectors::{Int32Vector, StringVector};
+
+        let src = StringVector::from(vec![
+            Some("1"),
+            Some("2"),
+            Some("a"),
+            None,
+            Some("4"),
+            Some("b"),
+        ]);
+        let dest_type = ConcreteDataType::int32_datatype();
+
+        let (vector, report) =
+            cast_vector_with_report(&src, &dest_type, &CastOption::default(), true).unwrap();
+        let expect = Int32Vector::from(vec![Some(1), Some(2), None, None, Some(4), None]);
+        assert_eq!(
+            &expect,
+            vector.as_any().downcast_ref::<Int32Vector>().unwrap()
+        );
+        assert_eq!(2, report.failure_count);
+        assert_eq!(vec![2, 5], report.failure_indices);
+
+        let (vector, report) =
+            cast_vector_with_report(&src, &dest_type, &CastOption::default(), false).unwrap();
+        assert_eq!(
+            &expect,
+            vector.as_any().downcast_ref::<Int32Vector>().unwrap()
+        );
+        assert_eq!(2, report.failure_count);
+        assert!(report.failure_indices.is_empty());
+    }
+
+    #[test]
+    fn test_cast_decimal128_to_string() {
+        let dest_type = ConcreteDataType::string_datatype();
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        for (decimal, expect) in [
+            (Decimal128::new(150, 5, 2), "1.50"),
+            (Decimal128::new(-150, 5, 2), "-1.50"),
+            (Decimal128::new(1500, 10, 3), "1.500"),
+            (Decimal128::new(5, 5, 2), "0.05"),
+            (Decimal128::new(0, 5, 2), "0.00"),
+            (Decimal128::new(100, 5, 0), "100"),
+        ] {
+            let res = cast_with_opt(Value::Decimal128(decimal), &dest_type, &cast_option).unwrap();
+            assert_eq!(Value::String(expect.into()), res);
+        }
+    }
+
+    #[test]
+    fn test_cast_boolean_vector_to_integer() {
+        use crate::vectors::{BooleanVector, Int8Vector, UInt64Vector};
+
+        let src = BooleanVector::from(vec![Some(true), Some(false), None]);
+        let cast_option = CastOption {
+            strict: true,
+            ..Default::default()
+        };
+        let vector =
+            cast_vector_with_opt(&src, &ConcreteDataType::int8_datatype(), &cast_option).unwrap();
+        assert_eq!(
+            &Int8Vector::from(vec![Some(1), Some(0), None]),
+            vector.as_any().downcast_ref::<Int8Vector>().unwrap()
+        );
+        let vector =
+            cast_vector_with_opt(&src, &ConcreteDataType::uint64_datatype(), &cast_option).unwrap();
+        assert_eq!(
+            &UInt64Vector::from(vec![Some(1), Some(0), None]),
+            vector.as_any().downcast_ref::<UInt64Vector>().unwrap()
+        );
+
+        // Same as casting the values one by one.
+        for dest_type in [
+            ConcreteDataType::int8_datatype(),
+            ConcreteDataType::int16_datatype(),
+            ConcreteDataType::int32_datatype(),
+            ConcreteDataType::int64_datatype(),
+            ConcreteDataType::uint8_datatype(),
+            ConcreteDataType::uint16_datatype(),
+            ConcreteDataType::uint32_datatype(),
+            ConcreteDataType::uint64_datatype(),
+        ] {
+            let vector = cast_vector_with_opt(&src, &dest_type, &cast_option).unwrap();
+            assert_eq!(dest_type, vector.data_type());
+            for i in 0..src.len() {
+                assert_eq!(
+                    cast_with_opt(src.get(i), &dest_type, &cast_option).unwrap(),
+                    vector.get(i)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_cast_to_null_type() {
+        let dest_type = ConcreteDataType::null_datatype();
+        for cast_option in [
+            CastOption::default(),
+            CastOption {
+                strict: true,
+                reinterpret: true,
+                ..Default::default()
+            },
+        ] {
+            for src_value in [
+                Value::Int64(5),
+                Value::String("5".into()),
+                Value::Boolean(true),
+                Value::Null,
+            ] {
+                let res = cast_with_opt(src_value, &dest_type, &cast_option).unwrap();
+                assert_eq!(Value::Null, res);
+            }
+        }
+    }
+
+    #[test]
+    fn test_date_to_components() {
+        let date = Date::from_str_utc("2024-02-29").unwrap();
+        assert_eq!(Some((2024, 2, 29)), date_to_components(date));
+        let date = Date::from_str_utc("2024-03-01").unwrap();
+        assert_eq!(Some((2024, 3, 1)), date_to_components(date));
+        assert_eq!(Some((1970, 1, 1)), date_to_components(Date::new(0)));
+        assert_eq!(Some((1969, 12, 31)), date_to_components(Date::new(-1)));
+        assert_eq!(None, date_to_components(Date::new(200_000_000)));
     }
 }
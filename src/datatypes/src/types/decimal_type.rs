@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::str::FromStr;
+
 use arrow_schema::DataType as ArrowDataType;
 use common_decimal::decimal128::DECIMAL128_MAX_PRECISION;
 use common_decimal::Decimal128;
@@ -53,6 +55,104 @@ impl Decimal128Type {
     pub fn scale(&self) -> i8 {
         self.scale
     }
+
+    /// Parse the string to a decimal of this type, rescaling it to the scale of this type.
+    /// Extra fractional digits are truncated toward zero.
+    ///
+    /// Return None if the string is not a decimal or the value overflows the precision.
+    fn parse_str(&self, s: &str) -> Option<Decimal128> {
+        let parsed = Decimal128::from_str(s).ok()?;
+        self.rescale_value(&parsed)
+    }
+
+    /// Returns true if the value fits this type after rescaling it to the scale of
+    /// this type. Extra fractional digits are truncated toward zero, so 1.239 fits
+    /// `Decimal(3, 2)` as 1.23.
+    pub fn can_hold(&self, value: &Decimal128) -> bool {
+        self.rescale_value(value).is_some()
+    }
+
+    /// Rescale the value to the scale of this type, truncating extra fractional digits.
+    /// Return None if the value overflows the precision.
+    fn rescale_value(&self, value: &Decimal128) -> Option<Decimal128> {
+        let value = Decimal128::new(value.val(), DECIMAL128_MAX_PRECISION, value.scale())
+            .rescale(self.scale)
+            .ok()?
+            .val();
+        (value.unsigned_abs() < 10_u128.pow(self.precision as u32))
+            .then(|| Decimal128::new(value, self.precision, self.scale))
+    }
+
+    /// Convert the float to a decimal of this type, rounding it half-up (away from zero)
+    /// to the scale of this type. The float is converted from its shortest decimal
+    /// representation `repr`, so `2.675` is rounded to `2.68` instead of `2.67`.
+    ///
+    /// Return None if the value overflows the precision.
+    fn round_float(&self, repr: &str) -> Option<Decimal128> {
+        // Only the first dropped digit decides the rounding, so the digits after it are
+        // truncated. This also keeps tiny floats like `1e-40` within the max scale.
+        let repr = match repr.find('.') {
+            Some(dot) => &repr[..repr.len().min(dot + self.scale as usize + 2)],
+            None => repr,
+        };
+        let parsed = Decimal128::from_str(repr).ok()?;
+        let value = if parsed.scale() <= self.scale {
+            Decimal128::new(parsed.val(), DECIMAL128_MAX_PRECISION, parsed.scale())
+                .rescale(self.scale)
+                .ok()?
+                .val()
+        } else {
+            let factor = 10_i128.checked_pow((parsed.scale() - self.scale) as u32)?;
+            let (quotient, remainder) = (parsed.val() / factor, parsed.val() % factor);
+            if remainder.unsigned_abs() * 2 >= factor.unsigned_abs() {
+                quotient + parsed.val().signum()
+            } else {
+                quotient
+            }
+        };
+        (value.unsigned_abs() < 10_u128.pow(self.precision as u32))
+            .then(|| Decimal128::new(value, self.precision, self.scale))
+    }
+
+    /// Convert the integer to a decimal of this type, e.g. 1 is converted to 1.00
+    /// (value 100) for scale 2.
+    ///
+    /// Return None if the value overflows the precision.
+    fn scale_integer(&self, v: i128) -> Option<Decimal128> {
+        let value = 10_i128
+            .checked_pow(self.scale as u32)
+            .and_then(|factor| v.checked_mul(factor))?;
+        (value.unsigned_abs() < 10_u128.pow(self.precision as u32))
+            .then(|| Decimal128::new(value, self.precision, self.scale))
+    }
+}
+
+/// Add two decimals, returning the sum and its inferred type.
+///
+/// The result scale is the max scale of the operands, and the result precision
+/// is `max(p1 - s1, p2 - s2) + max(s1, s2) + 1`, capped at [DECIMAL128_MAX_PRECISION].
+///
+/// Return None if the sum overflows the result precision.
+pub fn decimal_add(lhs: Decimal128, rhs: Decimal128) -> Option<(Decimal128, Decimal128Type)> {
+    let scale = lhs.scale().max(rhs.scale());
+    let integral_digits = (lhs.precision() as i16 - lhs.scale() as i16)
+        .max(rhs.precision() as i16 - rhs.scale() as i16);
+    let precision = (integral_digits + scale as i16 + 1).min(DECIMAL128_MAX_PRECISION as i16) as u8;
+
+    let align = |v: Decimal128| {
+        10_i128
+            .checked_pow((scale - v.scale()) as u32)
+            .and_then(|factor| v.val().checked_mul(factor))
+    };
+    let value = align(lhs)?.checked_add(align(rhs)?)?;
+    if value.unsigned_abs() >= 10_u128.pow(precision as u32) {
+        return None;
+    }
+
+    Some((
+        Decimal128::new(value, precision, scale),
+        Decimal128Type::new(precision, scale),
+    ))
 }
 
 impl DataType for Decimal128Type {
@@ -85,7 +185,70 @@ impl DataType for Decimal128Type {
         match val {
             Value::Null => Some(Value::Null),
             Value::Decimal128(_) => Some(val),
+            Value::String(s) => self.parse_str(s.as_utf8()).map(Value::Decimal128),
+            Value::Float32(f) if f.is_finite() => {
+                self.round_float(&f.0.to_string()).map(Value::Decimal128)
+            }
+            Value::Float64(f) if f.is_finite() => {
+                self.round_float(&f.0.to_string()).map(Value::Decimal128)
+            }
+            Value::Int8(v) => self.scale_integer(v as i128).map(Value::Decimal128),
+            Value::Int16(v) => self.scale_integer(v as i128).map(Value::Decimal128),
+            Value::Int32(v) => self.scale_integer(v as i128).map(Value::Decimal128),
+            Value::Int64(v) => self.scale_integer(v as i128).map(Value::Decimal128),
+            Value::UInt8(v) => self.scale_integer(v as i128).map(Value::Decimal128),
+            Value::UInt16(v) => self.scale_integer(v as i128).map(Value::Decimal128),
+            Value::UInt32(v) => self.scale_integer(v as i128).map(Value::Decimal128),
+            Value::UInt64(v) => self.scale_integer(v as i128).map(Value::Decimal128),
             _ => None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_add() {
+        // 1.5 + 2.25 = 3.75
+        let (value, data_type) =
+            decimal_add(Decimal128::new(15, 2, 1), Decimal128::new(225, 3, 2)).unwrap();
+        assert_eq!(Decimal128::new(375, 4, 2), value);
+        assert_eq!("3.75", value.to_string());
+        assert_eq!(Decimal128Type::new(4, 2), data_type);
+
+        // 99.9 + (-0.01) = 99.89
+        let (value, data_type) =
+            decimal_add(Decimal128::new(999, 3, 1), Decimal128::new(-1, 2, 2)).unwrap();
+        assert_eq!("99.89", value.to_string());
+        assert_eq!(Decimal128Type::new(5, 2), data_type);
+
+        // The precision is capped.
+        let max = Decimal128::new(10_i128.pow(38) - 1, 38, 0);
+        let (_, data_type) = decimal_add(max, Decimal128::new(-1, 1, 0)).unwrap();
+        assert_eq!(Decimal128Type::new(38, 0), data_type);
+        // overflow
+        assert!(decimal_add(max, Decimal128::new(1, 1, 0)).is_none());
+    }
+
+    #[test]
+    fn test_decimal128_type_can_hold() {
+        let data_type = Decimal128Type::new(5, 2);
+        // 123.45 fits
+        assert!(data_type.can_hold(&Decimal128::new(12345, 5, 2)));
+        // 12.3 is upscaled to 12.30
+        assert!(data_type.can_hold(&Decimal128::new(123, 3, 1)));
+        // 1234.5 overflows the precision
+        assert!(!data_type.can_hold(&Decimal128::new(12345, 5, 1)));
+        assert!(!data_type.can_hold(&Decimal128::new(-100000, 6, 2)));
+        // 1.239 is downscaled to 1.23
+        assert!(data_type.can_hold(&Decimal128::new(1239, 4, 3)));
+        // 999.999 is downscaled to 999.99
+        assert!(data_type.can_hold(&Decimal128::new(999999, 6, 3)));
+        // 1000.001 is downscaled to 1000.00, which overflows the precision
+        assert!(!data_type.can_hold(&Decimal128::new(1000001, 7, 3)));
+        // a large value overflows when upscaling
+        assert!(!Decimal128Type::new(38, 10).can_hold(&Decimal128::new(i128::MAX, 38, 0)));
+    }
+}
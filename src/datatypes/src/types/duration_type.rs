@@ -60,6 +60,11 @@ impl DurationType {
         }
     }
 
+    /// Creates duration type from arrow `TimeUnit`, the reverse of `as_arrow_type`.
+    pub fn from_arrow_unit(unit: ArrowTimeUnit) -> Self {
+        Self::from_unit(unit.into())
+    }
+
     /// Returns the [`TimeUnit`] of this type.
     pub fn unit(&self) -> TimeUnit {
         match self {
@@ -71,6 +76,34 @@ impl DurationType {
     }
 }
 
+/// Convert fractional seconds precision to [TimeUnit]. Supported precisions are:
+/// - 0: second
+/// - 3: millisecond
+/// - 6: microsecond
+/// - 9: nanosecond
+pub fn precision_to_time_unit(precision: u64) -> error::Result<TimeUnit> {
+    match precision {
+        0 => Ok(TimeUnit::Second),
+        3 => Ok(TimeUnit::Millisecond),
+        6 => Ok(TimeUnit::Microsecond),
+        9 => Ok(TimeUnit::Nanosecond),
+        _ => error::InvalidDurationPrecisionSnafu { precision }.fail(),
+    }
+}
+
+/// Parse the duration type from a spec like `duration(3)`, the precision in the
+/// parentheses is converted by [precision_to_time_unit].
+pub fn parse_duration_type(spec: &str) -> error::Result<DurationType> {
+    let precision = spec
+        .trim()
+        .split_once('(')
+        .filter(|(name, _)| name.trim_end().eq_ignore_ascii_case("duration"))
+        .and_then(|(_, rest)| rest.strip_suffix(')'))
+        .and_then(|precision| precision.trim().parse::<u64>().ok())
+        .context(error::InvalidDurationTypeSnafu { spec })?;
+    precision_to_time_unit(precision).map(DurationType::from_unit)
+}
+
 macro_rules! impl_data_type_for_duration {
     ($unit: ident) => {
         paste! {
@@ -189,6 +222,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_precision_to_time_unit() {
+        assert_eq!(TimeUnit::Second, precision_to_time_unit(0).unwrap());
+        assert_eq!(TimeUnit::Millisecond, precision_to_time_unit(3).unwrap());
+        assert_eq!(TimeUnit::Microsecond, precision_to_time_unit(6).unwrap());
+        assert_eq!(TimeUnit::Nanosecond, precision_to_time_unit(9).unwrap());
+        assert_eq!(
+            "Invalid duration precision: 2",
+            precision_to_time_unit(2).unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_type() {
+        assert_eq!(
+            DurationType::Second(DurationSecondType),
+            parse_duration_type("duration(0)").unwrap()
+        );
+        assert_eq!(
+            DurationType::Millisecond(DurationMillisecondType),
+            parse_duration_type("duration(3)").unwrap()
+        );
+        assert_eq!(
+            DurationType::Microsecond(DurationMicrosecondType),
+            parse_duration_type(" DURATION ( 6 ) ").unwrap()
+        );
+        assert_eq!(
+            DurationType::Nanosecond(DurationNanosecondType),
+            parse_duration_type("duration(9)").unwrap()
+        );
+
+        assert_eq!(
+            "Invalid duration precision: 2",
+            parse_duration_type("duration(2)").unwrap_err().to_string()
+        );
+        for spec in [
+            "",
+            "duration",
+            "duration()",
+            "duration(a)",
+            "duration(-3)",
+            "duration(3",
+            "interval(3)",
+            "durations(3)",
+        ] {
+            assert_eq!(
+                format!("Invalid duration type: {spec}"),
+                parse_duration_type(spec).unwrap_err().to_string()
+            );
+        }
+    }
+
     #[test]
     fn test_from_unit() {
         assert_eq!(
@@ -211,4 +296,22 @@ mod tests {
             DurationType::from_unit(TimeUnit::Nanosecond)
         );
     }
+
+    #[test]
+    fn test_from_arrow_unit() {
+        for unit in [
+            ArrowTimeUnit::Second,
+            ArrowTimeUnit::Millisecond,
+            ArrowTimeUnit::Microsecond,
+            ArrowTimeUnit::Nanosecond,
+        ] {
+            let duration_type = DurationType::from_arrow_unit(unit.clone());
+            assert_eq!(ArrowDataType::Duration(unit), duration_type.as_arrow_type());
+        }
+
+        assert_eq!(
+            DurationType::Millisecond(DurationMillisecondType),
+            DurationType::from_arrow_unit(ArrowTimeUnit::Millisecond)
+        );
+    }
 }
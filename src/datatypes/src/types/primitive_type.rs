@@ -16,7 +16,7 @@ use std::cmp::Ordering;
 use std::fmt;
 
 use arrow::datatypes::{ArrowNativeType, ArrowPrimitiveType, DataType as ArrowDataType};
-use common_time::interval::IntervalUnit;
+use common_time::interval::{IntervalUnit, NANOS_PER_DAY, NANOS_PER_MILLI};
 use common_time::{Date, DateTime};
 use num::NumCast;
 use serde::{Deserialize, Serialize};
@@ -387,7 +387,13 @@ impl DataType for Int64Type {
             Value::Timestamp(v) => Some(Value::Int64(v.value())),
             Value::Time(v) => Some(Value::Int64(v.value())),
             Value::Interval(v) => match v.unit() {
-                IntervalUnit::DayTime => Some(Value::Int64(v.to_i64())),
+                // Total milliseconds of the day-time interval.
+                IntervalUnit::DayTime => {
+                    let (_, days, nsecs) = v.to_month_day_nano();
+                    let millis =
+                        (days as i64) * (NANOS_PER_DAY / NANOS_PER_MILLI) + nsecs / NANOS_PER_MILLI;
+                    Some(Value::Int64(millis))
+                }
                 IntervalUnit::YearMonth => None,
                 IntervalUnit::MonthDayNano => None,
             },
@@ -51,9 +51,10 @@ pub use date::{DateVector, DateVectorBuilder};
 pub use datetime::{DateTimeVector, DateTimeVectorBuilder};
 pub use decimal::{Decimal128Vector, Decimal128VectorBuilder};
 pub use duration::{
-    DurationMicrosecondVector, DurationMicrosecondVectorBuilder, DurationMillisecondVector,
-    DurationMillisecondVectorBuilder, DurationNanosecondVector, DurationNanosecondVectorBuilder,
-    DurationSecondVector, DurationSecondVectorBuilder,
+    duration_vector_to_timestamp, duration_vectors_equal, DurationMicrosecondVector,
+    DurationMicrosecondVectorBuilder, DurationMillisecondVector, DurationMillisecondVectorBuilder,
+    DurationNanosecondVector, DurationNanosecondVectorBuilder, DurationSecondVector,
+    DurationSecondVectorBuilder,
 };
 pub use helper::Helper;
 pub use interval::{
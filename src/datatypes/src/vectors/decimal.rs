@@ -14,19 +14,22 @@
 
 use std::any::Any;
 use std::fmt::Debug;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use arrow_array::builder::{ArrayBuilder, Decimal128Builder};
 use arrow_array::iterator::ArrayIter;
+use arrow_array::types::{Decimal128Type as ArrowDecimal128Type, DecimalType};
 use arrow_array::{Array, ArrayRef, Decimal128Array};
 use common_decimal::decimal128::{DECIMAL128_DEFAULT_SCALE, DECIMAL128_MAX_PRECISION};
 use common_decimal::Decimal128;
-use snafu::{OptionExt, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 
 use crate::arrow::datatypes::DataType as ArrowDataType;
 use crate::data_type::ConcreteDataType;
 use crate::error::{
-    self, CastTypeSnafu, InvalidPrecisionOrScaleSnafu, Result, ValueExceedsPrecisionSnafu,
+    self, CastTypeSnafu, InvalidPrecisionOrScaleSnafu, ParseDecimalSnafu, RescaleDecimalSnafu,
+    Result, ValueExceedsPrecisionSnafu,
 };
 use crate::prelude::{ScalarVector, ScalarVectorBuilder};
 use crate::serialize::Serializable;
@@ -103,6 +106,28 @@ impl Decimal128Vector {
             .with_precision_and_scale(precision, scale)
     }
 
+    /// Returns a Decimal vector with the same precision as self, rescaling
+    /// every value to `new_scale` by [Decimal128::rescale], nulls are kept.
+    ///
+    /// Return error on the first value which overflows the precision after rescaling.
+    ///
+    /// For example:
+    /// value = 123, precision = 5, scale = 2, rescale to scale 4 is 12300.
+    pub fn rescale(&self, new_scale: i8) -> Result<Self> {
+        let precision = self.precision();
+        let array = self
+            .iter_data()
+            .map(|v| v.map(|v| v.rescale(new_scale).map(|v| v.val())).transpose())
+            .collect::<std::result::Result<Decimal128Array, _>>()
+            .context(RescaleDecimalSnafu { scale: new_scale })?
+            .with_precision_and_scale(precision, new_scale)
+            .context(InvalidPrecisionOrScaleSnafu {
+                precision,
+                scale: new_scale,
+            })?;
+        Ok(Self { array })
+    }
+
     /// Return decimal value as string
     pub fn value_as_string(&self, idx: usize) -> String {
         self.array.value_as_string(idx)
@@ -383,6 +408,40 @@ impl Decimal128VectorBuilder {
             mutable_array,
         })
     }
+
+    /// Parse the string to a decimal with the given precision and scale and push it,
+    /// extra fractional digits are truncated toward zero. Push NULL if `s` is None.
+    ///
+    /// The precision and scale should be the same as the builder's.
+    /// Return error if the string is not a decimal or the value exceeds the precision.
+    ///
+    /// For example:
+    /// "12.5" with precision 10 and scale 2 pushes 1250.
+    pub fn push_str(&mut self, s: Option<&str>, precision: u8, scale: i8) -> Result<()> {
+        let Some(s) = s else {
+            self.push_null();
+            return Ok(());
+        };
+        ensure!(
+            precision == self.precision && scale == self.scale,
+            CastTypeSnafu {
+                msg: format!(
+                    "Failed to push decimal({}, {}) to the builder of decimal({}, {})",
+                    precision, scale, self.precision, self.scale
+                ),
+            }
+        );
+
+        let parsed = Decimal128::from_str(s).context(ParseDecimalSnafu { raw: s })?;
+        let value = Decimal128::new(parsed.val(), DECIMAL128_MAX_PRECISION, parsed.scale())
+            .rescale(scale)
+            .context(RescaleDecimalSnafu { scale })?
+            .val();
+        ArrowDecimal128Type::validate_decimal_precision(value, precision)
+            .context(ValueExceedsPrecisionSnafu { precision })?;
+        self.mutable_array.append_value(value);
+        Ok(())
+    }
 }
 
 vectors::impl_try_from_arrow_array_for_vector!(Decimal128Array, Decimal128Vector);
@@ -534,6 +593,47 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_decimal128_vector_builder_push_str() {
+        let mut builder = Decimal128VectorBuilder::with_capacity(4)
+            .with_precision_and_scale(5, 2)
+            .unwrap();
+        let inputs = [
+            Some("12.5"),
+            None,
+            Some("-0.999"),
+            Some("abc"),
+            Some("1000"),
+        ];
+        let results = inputs
+            .iter()
+            .map(|s| builder.push_str(*s, 5, 2))
+            .collect::<Vec<_>>();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(results[2].is_ok());
+        assert_eq!(
+            "Failed to parse decimal from string: abc",
+            results[3].as_ref().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "Value exceeds the precision 5 bound",
+            results[4].as_ref().unwrap_err().to_string()
+        );
+        // mismatched precision and scale
+        assert!(builder.push_str(Some("1"), 10, 2).is_err());
+
+        let vector = builder.finish();
+        assert_eq!(
+            vec![
+                Some(Decimal128::new(1250, 5, 2)),
+                None,
+                Some(Decimal128::new(-99, 5, 2)),
+            ],
+            vector.iter_data().collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn test_cast_to_decimal128() {
         let vector = Int8Vector::from_values(vec![1, 2, 3, 4, 100]);
@@ -575,4 +675,29 @@ pub mod tests {
         assert_eq!(vector.len(), 4);
         assert_eq!(builder.len(), 4);
     }
+
+    #[test]
+    fn test_decimal128_vector_rescale() {
+        let vector = Decimal128Vector::from(vec![Some(123), None, Some(-4500)])
+            .with_precision_and_scale(6, 2)
+            .unwrap();
+        let rescaled = vector.rescale(4).unwrap();
+        assert_eq!(6, rescaled.precision());
+        assert_eq!(4, rescaled.scale());
+        assert_eq!(
+            vec![
+                Some(Decimal128::new(12300, 6, 4)),
+                None,
+                Some(Decimal128::new(-450000, 6, 4)),
+            ],
+            rescaled.iter_data().collect::<Vec<_>>()
+        );
+        assert_eq!("1.2300", rescaled.value_as_string(0));
+
+        // 9999.99 can't be represented by decimal(6, 4)
+        let vector = Decimal128Vector::from(vec![Some(1), None, Some(999999)])
+            .with_precision_and_scale(6, 2)
+            .unwrap();
+        assert!(vector.rescale(4).is_err());
+    }
 }
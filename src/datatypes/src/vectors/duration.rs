@@ -12,10 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_time::timestamp::TimeUnit;
+use common_time::{Duration, Timestamp};
+use paste::paste;
+use snafu::{ensure, OptionExt};
+
+use crate::data_type::{ConcreteDataType, DataType};
+use crate::error::{self, Result};
 use crate::types::{
     DurationMicrosecondType, DurationMillisecondType, DurationNanosecondType, DurationSecondType,
 };
-use crate::vectors::{PrimitiveVector, PrimitiveVectorBuilder};
+use crate::value::Value;
+use crate::vectors::{PrimitiveVector, PrimitiveVectorBuilder, Vector, VectorRef};
 
 pub type DurationSecondVector = PrimitiveVector<DurationSecondType>;
 pub type DurationSecondVectorBuilder = PrimitiveVectorBuilder<DurationSecondType>;
@@ -28,3 +36,214 @@ pub type DurationMicrosecondVectorBuilder = PrimitiveVectorBuilder<DurationMicro
 
 pub type DurationNanosecondVector = PrimitiveVector<DurationNanosecondType>;
 pub type DurationNanosecondVectorBuilder = PrimitiveVectorBuilder<DurationNanosecondType>;
+
+macro_rules! impl_aggregation_for_duration_vector {
+    ($unit: ident) => {
+        paste! {
+            impl [<Duration $unit Vector>] {
+                /// Returns the minimum duration of the vector, skipping nulls.
+                /// Returns `None` if the vector is empty or all values are null.
+                pub fn min_duration(&self) -> Option<Duration> {
+                    self.as_arrow()
+                        .iter()
+                        .flatten()
+                        .min()
+                        .map(|v| Duration::new(v, TimeUnit::$unit))
+                }
+
+                /// Returns the maximum duration of the vector, skipping nulls.
+                /// Returns `None` if the vector is empty or all values are null.
+                pub fn max_duration(&self) -> Option<Duration> {
+                    self.as_arrow()
+                        .iter()
+                        .flatten()
+                        .max()
+                        .map(|v| Duration::new(v, TimeUnit::$unit))
+                }
+
+                /// Returns the difference between the maximum and the minimum duration
+                /// of the vector, skipping nulls.
+                /// Returns `None` if all values are null or the difference overflows.
+                pub fn range_duration(&self) -> Option<Duration> {
+                    let min = self.min_duration()?;
+                    let max = self.max_duration()?;
+                    max.value()
+                        .checked_sub(min.value())
+                        .map(|v| Duration::new(v, TimeUnit::$unit))
+                }
+            }
+        }
+    };
+}
+
+impl_aggregation_for_duration_vector!(Second);
+impl_aggregation_for_duration_vector!(Millisecond);
+impl_aggregation_for_duration_vector!(Microsecond);
+impl_aggregation_for_duration_vector!(Nanosecond);
+
+/// Returns true if the two duration vectors have the same length and equal elements,
+/// the elements are compared after normalizing their units, e.g. `1s` equals `1000ms`.
+/// Nulls are only equal to nulls.
+///
+/// Returns error if any of the vectors is not a duration vector.
+pub fn duration_vectors_equal(a: &dyn Vector, b: &dyn Vector) -> Result<bool> {
+    for vector in [a, b] {
+        ensure!(
+            matches!(vector.data_type(), ConcreteDataType::Duration(_)),
+            error::UnsupportedOperationSnafu {
+                op: "comparing durations",
+                vector_type: vector.vector_type_name(),
+            }
+        );
+    }
+    if a.len() != b.len() {
+        return Ok(false);
+    }
+
+    let equal = (0..a.len()).all(|i| match (a.get(i), b.get(i)) {
+        (Value::Duration(x), Value::Duration(y)) => x == y,
+        (x, y) => x.is_null() && y.is_null(),
+    });
+    Ok(equal)
+}
+
+/// Converts the duration vector to a timestamp vector by adding each duration to the
+/// `epoch`. The unit of the timestamps is the finer one of the durations and the epoch,
+/// so no precision is lost. Nulls are kept as nulls.
+///
+/// Returns error if the vector is not a duration vector or any timestamp overflows.
+pub fn duration_vector_to_timestamp(vector: &dyn Vector, epoch: Timestamp) -> Result<VectorRef> {
+    let ConcreteDataType::Duration(duration_type) = vector.data_type() else {
+        return error::UnsupportedOperationSnafu {
+            op: "converting durations to timestamps",
+            vector_type: vector.vector_type_name(),
+        }
+        .fail();
+    };
+    let unit = if duration_type.unit().factor() < epoch.unit().factor() {
+        duration_type.unit()
+    } else {
+        epoch.unit()
+    };
+
+    let overflow = |duration: &Duration| {
+        error::CastTypeSnafu {
+            msg: format!("Timestamp overflows when adding duration {duration} to epoch {epoch:?}"),
+        }
+        .build()
+    };
+    let epoch_value = epoch
+        .convert_to(unit)
+        .with_context(|| error::CastTypeSnafu {
+            msg: format!("Epoch {epoch:?} overflows in unit {unit:?}"),
+        })?
+        .value();
+
+    let mut builder =
+        ConcreteDataType::timestamp_datatype(unit).create_mutable_vector(vector.len());
+    for i in 0..vector.len() {
+        match vector.get(i) {
+            Value::Duration(duration) => {
+                let value = duration
+                    .convert_to(unit)
+                    .and_then(|d| d.value().checked_add(epoch_value))
+                    .ok_or_else(|| overflow(&duration))?;
+                builder
+                    .push_value_ref(Value::Timestamp(Timestamp::new(value, unit)).as_value_ref());
+            }
+            _ => builder.push_null(),
+        }
+    }
+    Ok(builder.to_vector())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_vector_aggregation() {
+        let vector = DurationSecondVector::from(vec![Some(3), None, Some(-1), Some(7), None]);
+        assert_eq!(Some(Duration::new_second(-1)), vector.min_duration());
+        assert_eq!(Some(Duration::new_second(7)), vector.max_duration());
+        assert_eq!(Some(Duration::new_second(8)), vector.range_duration());
+
+        let vector = DurationMillisecondVector::from_values(vec![1500, 200]);
+        let min = vector.min_duration().unwrap();
+        assert_eq!(TimeUnit::Millisecond, min.unit());
+        assert_eq!(200, min.value());
+        assert_eq!(
+            Some(Duration::new_millisecond(1300)),
+            vector.range_duration()
+        );
+
+        let vector = DurationNanosecondVector::from_values(vec![i64::MIN, i64::MAX]);
+        assert_eq!(None, vector.range_duration());
+
+        // All nulls.
+        let vector = DurationMicrosecondVector::from(vec![None, None]);
+        assert_eq!(None, vector.min_duration());
+        assert_eq!(None, vector.max_duration());
+        assert_eq!(None, vector.range_duration());
+
+        // Empty.
+        let vector = DurationMicrosecondVector::from_values(vec![]);
+        assert_eq!(None, vector.min_duration());
+    }
+
+    #[test]
+    fn test_duration_vectors_equal() {
+        let a = DurationSecondVector::from(vec![Some(1), None, Some(-2)]);
+        let b = DurationMillisecondVector::from(vec![Some(1000), None, Some(-2000)]);
+        assert!(duration_vectors_equal(&a, &b).unwrap());
+        assert!(duration_vectors_equal(&b, &a).unwrap());
+
+        let c = DurationNanosecondVector::from(vec![Some(1_000_000_000), Some(0), Some(-2)]);
+        assert!(!duration_vectors_equal(&a, &c).unwrap());
+        // Different lengths.
+        let d = DurationMillisecondVector::from(vec![Some(1000)]);
+        assert!(!duration_vectors_equal(&a, &d).unwrap());
+        // Nulls at different positions.
+        let e = DurationMillisecondVector::from(vec![None, Some(0), Some(-2000)]);
+        assert!(!duration_vectors_equal(&a, &e).unwrap());
+
+        let not_duration = crate::vectors::Int64Vector::from_values(vec![1, 2, 3]);
+        assert!(duration_vectors_equal(&a, &not_duration).is_err());
+    }
+
+    #[test]
+    fn test_duration_vector_to_timestamp() {
+        let vector = DurationSecondVector::from(vec![Some(0), Some(90), None, Some(-1)]);
+        // 2024-01-01 00:00:00
+        let epoch = Timestamp::new_second(1704067200);
+        let timestamps = duration_vector_to_timestamp(&vector, epoch).unwrap();
+        assert_eq!(
+            ConcreteDataType::timestamp_second_datatype(),
+            timestamps.data_type()
+        );
+        let expect = crate::vectors::TimestampSecondVector::from(vec![
+            Some(1704067200),
+            Some(1704067290),
+            None,
+            Some(1704067199),
+        ]);
+        assert_eq!(&expect as &dyn Vector, &*timestamps);
+
+        // the finer unit is used
+        let epoch = Timestamp::new_millisecond(1500);
+        let timestamps = duration_vector_to_timestamp(&vector, epoch).unwrap();
+        assert_eq!(
+            Value::Timestamp(Timestamp::new_millisecond(91_500)),
+            timestamps.get(1)
+        );
+
+        // overflow
+        let vector = DurationSecondVector::from_values(vec![1]);
+        assert!(duration_vector_to_timestamp(&vector, Timestamp::new_second(i64::MAX)).is_err());
+        let vector = DurationSecondVector::from_values(vec![i64::MAX]);
+        assert!(duration_vector_to_timestamp(&vector, Timestamp::new_millisecond(0)).is_err());
+
+        let not_duration = crate::vectors::Int64Vector::from_values(vec![1]);
+        assert!(duration_vector_to_timestamp(&not_duration, epoch).is_err());
+    }
+}
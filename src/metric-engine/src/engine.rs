@@ -236,6 +236,19 @@ impl MetricEngine {
             .logical_regions(physical_region_id)
             .await
     }
+
+    /// Describes how a [ScanRequest] on the logical region is transformed
+    /// to the physical data region.
+    pub async fn describe_transform(
+        &self,
+        logical_region_id: RegionId,
+        request: ScanRequest,
+    ) -> Result<String> {
+        let physical_region_id = self.inner.get_physical_region_id(logical_region_id).await?;
+        self.inner
+            .describe_transform(physical_region_id, logical_region_id, request)
+            .await
+    }
 }
 
 struct MetricEngineInner {
@@ -12,23 +12,33 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use api::v1::SemanticType;
 use common_query::logical_plan::Expr;
 use common_recordbatch::SendableRecordBatchStream;
 use common_telemetry::{error, info, tracing};
-use datafusion::logical_expr;
-use snafu::{OptionExt, ResultExt};
+use datafusion::common::tree_node::{Transformed, TreeNode};
+use datafusion::common::ScalarValue;
+use datafusion::logical_expr::{self, BinaryExpr, Expr as DfExpr, Operator};
+use datatypes::data_type::DataType;
+use datatypes::types::cast::CastOption;
+use datatypes::types::cast_with_opt;
+use datatypes::value::Value;
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::metadata::{RegionMetadata, RegionMetadataBuilder, RegionMetadataRef};
 use store_api::metric_engine_consts::DATA_SCHEMA_TABLE_ID_COLUMN_NAME;
 use store_api::region_engine::RegionEngine;
 use store_api::storage::consts::ReservedColumnId;
-use store_api::storage::{RegionId, ScanRequest};
+use store_api::storage::{ColumnId, RegionId, ScanRequest};
 
 use crate::engine::MetricEngineInner;
 use crate::error::{
-    InvalidMetadataSnafu, LogicalRegionNotFoundSnafu, MitoReadOperationSnafu, Result,
+    DuplicateProjectionSnafu, InvalidMetadataSnafu, LogicalRegionNotFoundSnafu,
+    MitoReadOperationSnafu, Result,
 };
 use crate::utils;
 
@@ -68,15 +78,29 @@ impl MetricEngineInner {
         logical_region_id: RegionId,
         request: ScanRequest,
     ) -> Result<SendableRecordBatchStream> {
+        self.read_logical_region_with_origin(logical_region_id, request)
+            .await
+            .map(|(stream, _)| stream)
+    }
+
+    /// Reads the logical region, and returns the stream along with the id of
+    /// the data region that serves the request.
+    async fn read_logical_region_with_origin(
+        &self,
+        logical_region_id: RegionId,
+        request: ScanRequest,
+    ) -> Result<(SendableRecordBatchStream, RegionId)> {
         let physical_region_id = self.get_physical_region_id(logical_region_id).await?;
         let data_region_id = utils::to_data_region_id(physical_region_id);
         let request = self
             .transform_request(physical_region_id, logical_region_id, request)
             .await?;
-        self.mito
+        let stream = self
+            .mito
             .handle_query(data_region_id, request)
             .await
-            .context(MitoReadOperationSnafu)
+            .context(MitoReadOperationSnafu)?;
+        Ok((stream, data_region_id))
     }
 
     pub async fn load_region_metadata(&self, region_id: RegionId) -> Result<RegionMetadataRef> {
@@ -108,7 +132,10 @@ impl MetricEngineInner {
             .contains_key(&region_id)
     }
 
-    async fn get_physical_region_id(&self, logical_region_id: RegionId) -> Result<RegionId> {
+    pub(crate) async fn get_physical_region_id(
+        &self,
+        logical_region_id: RegionId,
+    ) -> Result<RegionId> {
         let state = &self.state.read().unwrap();
         state
             .get_physical_region_id(logical_region_id)
@@ -127,16 +154,37 @@ impl MetricEngineInner {
         logical_region_id: RegionId,
         mut request: ScanRequest,
     ) -> Result<ScanRequest> {
+        normalize_scan_request(&mut request);
+
+        let logical_columns = self
+            .load_logical_columns(physical_region_id, logical_region_id)
+            .await?;
+        let physical_metadata = self
+            .mito
+            .get_metadata(utils::to_data_region_id(physical_region_id))
+            .await
+            .context(MitoReadOperationSnafu)?;
+
         // transform projection
-        let physical_projection = if let Some(projection) = &request.projection {
-            self.transform_projection(physical_region_id, logical_region_id, projection)
-                .await?
-        } else {
-            self.default_projection(physical_region_id, logical_region_id)
-                .await?
+        let projected_logical_names = match &request.projection {
+            Some(projection) => projection
+                .iter()
+                .map(|i| logical_columns[*i].column_schema.name.as_str())
+                .collect::<Vec<_>>(),
+            None => logical_columns
+                .iter()
+                .map(|col| col.column_schema.name.as_str())
+                .collect(),
         };
+        let physical_projection = physical_columns(&physical_metadata, &projected_logical_names)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
         request.projection = Some(physical_projection);
 
+        // cast literals in filters to the types of physical columns
+        request.filters = cast_filter_literals(request.filters, &physical_metadata);
+
         // add table filter
         request
             .filters
@@ -145,6 +193,58 @@ impl MetricEngineInner {
         Ok(request)
     }
 
+    /// Describes how the [ScanRequest] on the logical region is transformed to
+    /// the physical data region, in a human-readable form.
+    ///
+    /// The description lists each projected column with its logical and physical
+    /// index, and the filters added by the transformation.
+    pub async fn describe_transform(
+        &self,
+        physical_region_id: RegionId,
+        logical_region_id: RegionId,
+        request: ScanRequest,
+    ) -> Result<String> {
+        let logical_columns = self
+            .load_logical_columns(physical_region_id, logical_region_id)
+            .await?;
+        let logical_projection = request
+            .projection
+            .clone()
+            .unwrap_or_else(|| (0..logical_columns.len()).collect());
+        let origin_filter_num = request.filters.len();
+
+        let request = self
+            .transform_request(physical_region_id, logical_region_id, request)
+            .await?;
+        // Safety: the transformed request always has a projection
+        let physical_projection = request.projection.unwrap();
+
+        let columns = logical_projection
+            .iter()
+            .zip(physical_projection.iter())
+            .map(|(logical_index, physical_index)| {
+                format!(
+                    "{}: {} -> {}",
+                    logical_columns[*logical_index].column_schema.name,
+                    logical_index,
+                    physical_index
+                )
+            })
+            .collect::<Vec<_>>();
+        let added_filters = request.filters[origin_filter_num..]
+            .iter()
+            .map(|filter| filter.df_expr().to_string())
+            .collect::<Vec<_>>();
+
+        Ok(format!(
+            "logical region {} -> data region {}, projection: [{}], added filters: [{}]",
+            logical_region_id,
+            utils::to_data_region_id(physical_region_id),
+            columns.join(", "),
+            added_filters.join(", ")
+        ))
+    }
+
     /// Generate a filter on the table id column.
     fn table_id_filter(&self, logical_region_id: RegionId) -> Expr {
         logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME)
@@ -161,29 +261,57 @@ impl MetricEngineInner {
         logical_region_id: RegionId,
         origin_projection: &[usize],
     ) -> Result<Vec<usize>> {
+        let projection = self
+            .transform_projection_with_ids(physical_region_id, logical_region_id, origin_projection)
+            .await?
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect();
+        Ok(projection)
+    }
+
+    /// Same as [Self::transform_projection], but returns the physical column
+    /// index together with the column id of each projected column.
+    pub async fn transform_projection_with_ids(
+        &self,
+        physical_region_id: RegionId,
+        logical_region_id: RegionId,
+        origin_projection: &[usize],
+    ) -> Result<Vec<(usize, ColumnId)>> {
         // project on logical columns
         let all_logical_columns = self
             .load_logical_columns(physical_region_id, logical_region_id)
             .await?;
         let projected_logical_names = origin_projection
             .iter()
-            .map(|i| all_logical_columns[*i].column_schema.name.clone())
+            .map(|i| all_logical_columns[*i].column_schema.name.as_str())
             .collect::<Vec<_>>();
 
         // generate physical projection
-        let mut physical_projection = Vec::with_capacity(origin_projection.len());
         let data_region_id = utils::to_data_region_id(physical_region_id);
         let physical_metadata = self
             .mito
             .get_metadata(data_region_id)
             .await
             .context(MitoReadOperationSnafu)?;
-        for name in projected_logical_names {
-            // Safety: logical columns is a strict subset of physical columns
-            physical_projection.push(physical_metadata.column_index_by_name(&name).unwrap());
-        }
 
-        Ok(physical_projection)
+        Ok(physical_columns(
+            &physical_metadata,
+            &projected_logical_names,
+        ))
+    }
+
+    /// Same as [Self::transform_projection], but rejects projections that
+    /// contain duplicate logical indices.
+    pub async fn transform_projection_strict(
+        &self,
+        physical_region_id: RegionId,
+        logical_region_id: RegionId,
+        origin_projection: &[usize],
+    ) -> Result<Vec<usize>> {
+        validate_projection(origin_projection)?;
+        self.transform_projection(physical_region_id, logical_region_id, origin_projection)
+            .await
     }
 
     /// Default projection for a logical region. Includes non-internal columns
@@ -194,22 +322,22 @@ impl MetricEngineInner {
     ) -> Result<Vec<usize>> {
         let logical_columns = self
             .load_logical_columns(physical_region_id, logical_region_id)
-            .await?
-            .into_iter()
-            .map(|col| col.column_schema.name);
-        let mut projection = Vec::with_capacity(logical_columns.len());
+            .await?;
+        let logical_names = logical_columns
+            .iter()
+            .map(|col| col.column_schema.name.as_str())
+            .collect::<Vec<_>>();
         let data_region_id = utils::to_data_region_id(physical_region_id);
         let physical_metadata = self
             .mito
             .get_metadata(data_region_id)
             .await
             .context(MitoReadOperationSnafu)?;
-        for name in logical_columns {
-            // Safety: logical columns is a strict subset of physical columns
-            projection.push(physical_metadata.column_index_by_name(&name).unwrap());
-        }
 
-        Ok(projection)
+        Ok(physical_columns(&physical_metadata, &logical_names)
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect())
     }
 
     pub async fn logical_region_metadata(
@@ -245,12 +373,137 @@ impl MetricEngineInner {
     }
 }
 
+/// Returns the index and the column id of each logical column in `names` in the
+/// physical region of `physical_metadata`.
+fn physical_columns(physical_metadata: &RegionMetadata, names: &[&str]) -> Vec<(usize, ColumnId)> {
+    names
+        .iter()
+        .map(|name| {
+            // Safety: logical columns is a strict subset of physical columns
+            let index = physical_metadata.column_index_by_name(name).unwrap();
+            (index, physical_metadata.column_metadatas[index].column_id)
+        })
+        .collect()
+}
+
+/// Sorts the filters of the [ScanRequest] by the structural hash of the expressions, so
+/// requests with the same filters in different orders are normalized to the same request.
+///
+/// Filters are conjunctive, so their order doesn't change the result of the scan.
+pub fn normalize_scan_request(request: &mut ScanRequest) {
+    request.filters.sort_by_cached_key(|filter| {
+        // The hasher uses fixed keys, so the order is the same across requests.
+        let mut hasher = DefaultHasher::new();
+        filter.df_expr().hash(&mut hasher);
+        hasher.finish()
+    });
+}
+
+/// Checks that the given projection doesn't contain duplicate indices.
+pub fn validate_projection(projection: &[usize]) -> Result<()> {
+    let mut seen = HashSet::with_capacity(projection.len());
+    for index in projection {
+        ensure!(
+            seen.insert(*index),
+            DuplicateProjectionSnafu { index: *index }
+        );
+    }
+    Ok(())
+}
+
+/// Cast the literal operands of comparisons in `filters` to the types of the compared
+/// columns in `metadata`, to avoid type mismatch when evaluating the filters.
+///
+/// Only lossless casts are rewritten, a literal that can't be cast exactly, e.g. `3.5`
+/// compared with an integer column, is kept as is so the result of the filter doesn't
+/// change. Filters that fail to rewrite are kept as is.
+pub fn cast_filter_literals(filters: Vec<Expr>, metadata: &RegionMetadata) -> Vec<Expr> {
+    filters
+        .into_iter()
+        .map(|filter| {
+            filter
+                .df_expr()
+                .clone()
+                .transform(&|expr| Ok(cast_comparison_literal(expr, metadata)))
+                .map(Expr::from)
+                .unwrap_or(filter)
+        })
+        .collect()
+}
+
+/// Cast the literal of a comparison between a column and a literal to the column type.
+fn cast_comparison_literal(expr: DfExpr, metadata: &RegionMetadata) -> Transformed<DfExpr> {
+    let DfExpr::BinaryExpr(BinaryExpr { left, op, right }) = expr else {
+        return Transformed::No(expr);
+    };
+    if !matches!(
+        op,
+        Operator::Eq
+            | Operator::NotEq
+            | Operator::Lt
+            | Operator::LtEq
+            | Operator::Gt
+            | Operator::GtEq
+    ) {
+        return Transformed::No(DfExpr::BinaryExpr(BinaryExpr { left, op, right }));
+    }
+
+    let (left, right) = match (*left, *right) {
+        (DfExpr::Column(column), DfExpr::Literal(value)) => {
+            let value = cast_literal(&column.name, value, metadata);
+            (DfExpr::Column(column), DfExpr::Literal(value))
+        }
+        (DfExpr::Literal(value), DfExpr::Column(column)) => {
+            let value = cast_literal(&column.name, value, metadata);
+            (DfExpr::Literal(value), DfExpr::Column(column))
+        }
+        (left, right) => (left, right),
+    };
+    Transformed::Yes(DfExpr::BinaryExpr(BinaryExpr {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }))
+}
+
+/// Cast the literal to the type of column `column_name` by [cast_with_opt] in strict mode.
+/// Returns the literal as is if the column is not found, the cast fails or the cast is
+/// lossy, i.e. the casted value doesn't cast back to the origin value.
+fn cast_literal(column_name: &str, value: ScalarValue, metadata: &RegionMetadata) -> ScalarValue {
+    let Some(column) = metadata.column_by_name(column_name) else {
+        return value;
+    };
+    let data_type = &column.column_schema.data_type;
+    if value.is_null() || value.data_type() == data_type.as_arrow_type() {
+        return value;
+    }
+    let Ok(origin) = Value::try_from(value.clone()) else {
+        return value;
+    };
+
+    let origin_type = origin.data_type();
+    let cast_option = CastOption {
+        strict: true,
+        ..Default::default()
+    };
+    cast_with_opt(origin.clone(), data_type, &cast_option)
+        .ok()
+        .filter(|casted| !casted.is_null())
+        .filter(|casted| {
+            cast_with_opt(casted.clone(), &origin_type, &cast_option)
+                .is_ok_and(|round_trip| round_trip == origin)
+        })
+        .and_then(|casted| casted.try_to_scalar_value(data_type).ok())
+        .unwrap_or(value)
+}
+
 #[cfg(test)]
 mod test {
     use store_api::region_request::RegionRequest;
 
     use super::*;
     use crate::engine::alter;
+    use crate::error::Error;
     use crate::test_util::{
         alter_logical_region_add_tag_columns, create_logical_region_request, TestEnv,
     };
@@ -314,4 +567,238 @@ mod test {
             .unwrap();
         assert_eq!(scan_req.projection.unwrap(), vec![0, 1, 4, 8, 9, 10, 11]);
     }
+
+    #[tokio::test]
+    async fn test_read_logical_region_with_origin() {
+        let env = TestEnv::new().await;
+        env.init_metric_region().await;
+
+        let logical_region_id = env.default_logical_region_id();
+        let physical_region_id = env.default_physical_region_id();
+
+        let (_, origin) = env
+            .metric()
+            .inner
+            .read_logical_region_with_origin(logical_region_id, ScanRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(utils::to_data_region_id(physical_region_id), origin);
+        assert!(utils::is_data_region_id(origin));
+    }
+
+    #[tokio::test]
+    async fn test_describe_transform() {
+        let env = TestEnv::new().await;
+        env.init_metric_region().await;
+
+        let logical_region_id = env.default_logical_region_id();
+        let physical_region_id = env.default_physical_region_id();
+
+        let scan_req = ScanRequest {
+            projection: Some(vec![0, 1, 2]),
+            ..Default::default()
+        };
+        let description = env
+            .metric()
+            .inner
+            .describe_transform(physical_region_id, logical_region_id, scan_req)
+            .await
+            .unwrap();
+
+        let logical_columns = env
+            .metric()
+            .inner
+            .load_logical_columns(physical_region_id, logical_region_id)
+            .await
+            .unwrap();
+        for column in &logical_columns[..3] {
+            assert!(
+                description.contains(&column.column_schema.name),
+                "{description}"
+            );
+        }
+        assert!(
+            description.contains(&format!(
+                "added filters: [{DATA_SCHEMA_TABLE_ID_COLUMN_NAME} = UInt32({})]",
+                logical_region_id.table_id()
+            )),
+            "{description}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transform_projection_strict() {
+        let env = TestEnv::new().await;
+        env.init_metric_region().await;
+
+        let logical_region_id = env.default_logical_region_id();
+        let physical_region_id = env.default_physical_region_id();
+
+        assert!(validate_projection(&[1, 0, 2]).is_ok());
+
+        // duplicate projection is allowed by default
+        env.metric()
+            .inner
+            .transform_projection(physical_region_id, logical_region_id, &[0, 0, 1])
+            .await
+            .unwrap();
+        let err = env
+            .metric()
+            .inner
+            .transform_projection_strict(physical_region_id, logical_region_id, &[0, 0, 1])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::DuplicateProjection { index: 0, .. }),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_default_projection() {
+        let env = TestEnv::new().await;
+        env.init_metric_region().await;
+
+        let logical_region_id = env.default_logical_region_id();
+        let physical_region_id = env.default_physical_region_id();
+
+        let default_projection = env
+            .metric()
+            .inner
+            .default_projection(physical_region_id, logical_region_id)
+            .await
+            .unwrap();
+        let request = env
+            .metric()
+            .inner
+            .transform_request(
+                physical_region_id,
+                logical_region_id,
+                ScanRequest::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(Some(default_projection), request.projection);
+    }
+
+    #[tokio::test]
+    async fn test_transform_projection_with_ids() {
+        let env = TestEnv::new().await;
+        env.init_metric_region().await;
+
+        let logical_region_id = env.default_logical_region_id();
+        let physical_region_id = env.default_physical_region_id();
+
+        let logical_columns = env
+            .metric()
+            .inner
+            .load_logical_columns(physical_region_id, logical_region_id)
+            .await
+            .unwrap();
+        let projection = (0..logical_columns.len()).rev().collect::<Vec<_>>();
+        let with_ids = env
+            .metric()
+            .inner
+            .transform_projection_with_ids(physical_region_id, logical_region_id, &projection)
+            .await
+            .unwrap();
+        let expected_ids = projection
+            .iter()
+            .map(|i| logical_columns[*i].column_id)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            expected_ids,
+            with_ids.iter().map(|(_, id)| *id).collect::<Vec<_>>()
+        );
+
+        let indices = env
+            .metric()
+            .inner
+            .transform_projection(physical_region_id, logical_region_id, &projection)
+            .await
+            .unwrap();
+        assert_eq!(
+            indices,
+            with_ids.iter().map(|(index, _)| *index).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_normalize_scan_request() {
+        let filters = [
+            logical_expr::col("a").eq(logical_expr::lit(1)),
+            logical_expr::col("b").lt(logical_expr::lit("x")),
+            logical_expr::col("a").eq(logical_expr::lit(1i64)),
+            logical_expr::col("c").is_null(),
+        ];
+        let mut request1 = ScanRequest {
+            filters: filters.iter().cloned().map(Expr::from).collect(),
+            ..Default::default()
+        };
+        let mut request2 = ScanRequest {
+            filters: filters.iter().rev().cloned().map(Expr::from).collect(),
+            ..Default::default()
+        };
+        assert_ne!(request1.filters, request2.filters);
+
+        normalize_scan_request(&mut request1);
+        normalize_scan_request(&mut request2);
+        assert_eq!(request1.filters, request2.filters);
+        assert_eq!(filters.len(), request1.filters.len());
+
+        // normalizing again changes nothing
+        let normalized = request1.filters.clone();
+        normalize_scan_request(&mut request1);
+        assert_eq!(normalized, request1.filters);
+    }
+
+    #[tokio::test]
+    async fn test_cast_filter_literals() {
+        let env = TestEnv::new().await;
+        env.init_metric_region().await;
+
+        let physical_region_id = env.default_physical_region_id();
+        let physical_metadata = env
+            .mito()
+            .get_metadata(utils::to_data_region_id(physical_region_id))
+            .await
+            .unwrap();
+
+        // the table id column is UInt32
+        let filters = vec![
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME)
+                .eq(logical_expr::lit("3"))
+                .into(),
+            logical_expr::lit("abc")
+                .lt(logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME))
+                .into(),
+            logical_expr::col("unknown")
+                .eq(logical_expr::lit("3"))
+                .into(),
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME)
+                .lt(logical_expr::lit(3.5f64))
+                .into(),
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME)
+                .eq(logical_expr::lit(3.0f64))
+                .into(),
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME)
+                .eq(logical_expr::lit(-1i64))
+                .into(),
+        ];
+        let filters = cast_filter_literals(filters, &physical_metadata);
+        let expected = [
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME).eq(logical_expr::lit(3u32)),
+            // the literal is kept if the cast fails
+            logical_expr::lit("abc").lt(logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME)),
+            logical_expr::col("unknown").eq(logical_expr::lit("3")),
+            // lossy casts are not rewritten
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME).lt(logical_expr::lit(3.5f64)),
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME).eq(logical_expr::lit(3u32)),
+            logical_expr::col(DATA_SCHEMA_TABLE_ID_COLUMN_NAME).eq(logical_expr::lit(-1i64)),
+        ];
+        assert_eq!(expected.len(), filters.len());
+        for (filter, expected) in filters.iter().zip(expected) {
+            assert_eq!(&expected, filter.df_expr());
+        }
+    }
 }
@@ -107,6 +107,9 @@ pub enum Error {
     #[snafu(display("Internal column {} is reserved", column))]
     InternalColumnOccupied { column: String, location: Location },
 
+    #[snafu(display("Duplicate index {} in projection", index))]
+    DuplicateProjection { index: usize, location: Location },
+
     #[snafu(display("Required table option is missing"))]
     MissingRegionOption { location: Location },
 
@@ -165,6 +168,7 @@ impl ErrorExt for Error {
 
         match self {
             InternalColumnOccupied { .. }
+            | DuplicateProjection { .. }
             | MissingRegionOption { .. }
             | ConflictRegionOption { .. }
             | ColumnTypeMismatch { .. }
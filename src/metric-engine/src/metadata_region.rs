@@ -173,7 +173,7 @@ impl MetadataRegion {
         physical_region_id: RegionId,
         logical_region_id: RegionId,
     ) -> Result<Vec<(String, ColumnMetadata)>> {
-        let metadata_region_id = utils::to_metadata_region_id(physical_region_id);
+        let metadata_region_id = utils::to_logical_metadata_region_id(physical_region_id);
         let region_column_prefix = Self::concat_column_key_prefix(logical_region_id);
 
         let mut columns = vec![];
@@ -16,6 +16,9 @@ use store_api::metric_engine_consts::{METRIC_DATA_REGION_GROUP, METRIC_METADATA_
 use store_api::storage::RegionId;
 
 /// Change the given [RegionId]'s region group to [METRIC_METADATA_REGION_GROUP].
+///
+/// The result is the id of the metadata region that stores the metadata of the
+/// logical regions on the physical region.
 pub fn to_metadata_region_id(region_id: RegionId) -> RegionId {
     let table_id = region_id.table_id();
     let region_sequence = region_id.region_sequence();
@@ -29,6 +32,27 @@ pub fn to_data_region_id(region_id: RegionId) -> RegionId {
     RegionId::with_group_and_seq(table_id, METRIC_DATA_REGION_GROUP, region_sequence)
 }
 
+/// Returns true if the given [RegionId]'s region group is [METRIC_DATA_REGION_GROUP].
+///
+/// As the data region group is the default group, the physical region id is also
+/// the id of its data region.
+pub fn is_data_region_id(region_id: RegionId) -> bool {
+    region_id.region_group() == METRIC_DATA_REGION_GROUP
+}
+
+/// Returns the id of the metadata region that stores the metadata of the logical
+/// regions on the physical region `physical_region_id`.
+///
+/// All logical regions on a physical region share its metadata region, so the
+/// result only depends on the physical region id, which must be a data region id.
+pub fn to_logical_metadata_region_id(physical_region_id: RegionId) -> RegionId {
+    debug_assert!(
+        is_data_region_id(physical_region_id),
+        "{physical_region_id} is not a physical region id"
+    );
+    to_metadata_region_id(physical_region_id)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -55,4 +79,63 @@ mod tests {
         let expected_region_id = RegionId::with_group_and_seq(1, METRIC_DATA_REGION_GROUP, 2);
         assert_eq!(to_data_region_id(region_id), expected_region_id);
     }
+
+    #[test]
+    fn test_is_data_region_id() {
+        let region_id = RegionId::new(1, 2);
+        assert!(is_data_region_id(region_id));
+        assert!(is_data_region_id(to_data_region_id(region_id)));
+        assert!(!is_data_region_id(to_metadata_region_id(region_id)));
+        assert!(!is_data_region_id(RegionId::with_group_and_seq(1, 243, 2)));
+    }
+
+    #[test]
+    fn test_to_logical_metadata_region_id() {
+        let physical_region_id = RegionId::new(1, 2);
+        let metadata_region_id = to_logical_metadata_region_id(physical_region_id);
+        assert_eq!(
+            to_metadata_region_id(physical_region_id),
+            metadata_region_id
+        );
+        assert!(!is_data_region_id(metadata_region_id));
+        // Back to the physical region id.
+        assert_eq!(physical_region_id, to_data_region_id(metadata_region_id));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_to_logical_metadata_region_id_not_physical() {
+        let metadata_region_id = to_metadata_region_id(RegionId::new(1, 2));
+        to_logical_metadata_region_id(metadata_region_id);
+    }
+
+    #[test]
+    fn test_region_id_round_trip() {
+        for physical_region_id in [
+            RegionId::new(1, 2),
+            RegionId::new(1024, 0),
+            RegionId::new(u32::MAX, 0xFFFFFF),
+        ] {
+            let data_region_id = to_data_region_id(physical_region_id);
+            assert_eq!(physical_region_id, data_region_id);
+
+            let metadata_region_id = to_metadata_region_id(physical_region_id);
+            assert_ne!(physical_region_id, metadata_region_id);
+            assert_eq!(physical_region_id.table_id(), metadata_region_id.table_id());
+            assert_eq!(
+                physical_region_id.region_sequence(),
+                metadata_region_id.region_sequence()
+            );
+            assert_eq!(physical_region_id, to_data_region_id(metadata_region_id));
+            assert_eq!(
+                metadata_region_id,
+                to_logical_metadata_region_id(physical_region_id)
+            );
+            assert_eq!(
+                metadata_region_id,
+                to_metadata_region_id(to_data_region_id(metadata_region_id))
+            );
+        }
+    }
 }
@@ -126,6 +126,7 @@ impl AccessLayer {
                         upload_path: file_path,
                         index_upload_path: index_file_path,
                         remote_store: self.object_store.clone(),
+                        skip_existing: false,
                     },
                     write_opts,
                 )
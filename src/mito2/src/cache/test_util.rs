@@ -33,7 +33,7 @@ pub(crate) fn parquet_meta() -> Arc<ParquetMetaData> {
 }
 
 /// Write a test parquet file to a buffer
-fn parquet_file_data() -> Vec<u8> {
+pub(crate) fn parquet_file_data() -> Vec<u8> {
     let col = Arc::new(Int64Array::from_iter_values([1, 2, 3])) as ArrayRef;
     let to_write = RecordBatch::try_from_iter([("col", col)]).unwrap();
 
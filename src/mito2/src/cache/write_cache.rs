@@ -14,18 +14,24 @@
 
 //! A write-through cache for remote object stores.
 
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use common_base::readable_size::ReadableSize;
-use common_telemetry::{debug, info};
+use common_telemetry::{debug, info, warn};
+use futures::{ready, AsyncRead};
 use object_store::manager::ObjectStoreManagerRef;
 use object_store::ObjectStore;
-use snafu::ResultExt;
+use snafu::{ensure, ResultExt};
+use store_api::storage::RegionId;
+use tokio_util::sync::CancellationToken;
 
 use crate::access_layer::{new_fs_object_store, SstWriteRequest};
 use crate::cache::file_cache::{FileCache, FileCacheRef, FileType, IndexKey, IndexValue};
-use crate::error::{self, Result};
+use crate::error::{self, Error, Result};
 use crate::metrics::{FLUSH_ELAPSED, UPLOAD_BYTES_TOTAL};
+use crate::sst::file::FileId;
 use crate::sst::index::intermediate::IntermediateManager;
 use crate::sst::index::IndexerBuilder;
 use crate::sst::parquet::writer::ParquetWriter;
@@ -44,6 +50,8 @@ pub struct WriteCache {
     object_store_manager: ObjectStoreManagerRef,
     /// Intermediate manager for inverted index.
     intermediate_manager: IntermediateManager,
+    /// Token to cancel the writes and uploads on shutdown.
+    cancel: CancellationToken,
 }
 
 pub type WriteCacheRef = Arc<WriteCache>;
@@ -64,6 +72,7 @@ impl WriteCache {
             file_cache: Arc::new(file_cache),
             object_store_manager,
             intermediate_manager,
+            cancel: CancellationToken::new(),
         })
     }
 
@@ -86,12 +95,32 @@ impl WriteCache {
         .await
     }
 
+    /// Cancels the in-flight and subsequent writes and uploads, so shutdown doesn't
+    /// wait for them. Cancelled uploads have their remote writers aborted.
+    pub(crate) fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
     /// Returns the file cache of the write cache.
     pub(crate) fn file_cache(&self) -> FileCacheRef {
         self.file_cache.clone()
     }
 
+    /// Returns true if the SST file of the region is still present in the local store.
+    #[allow(unused)]
+    pub(crate) async fn contains(&self, region_id: RegionId, file_id: FileId) -> bool {
+        let key = IndexKey::new(region_id, file_id, FileType::Parquet);
+        let cache_path = self.file_cache.cache_file_path(key);
+        self.file_cache
+            .local_store()
+            .stat(&cache_path)
+            .await
+            .is_ok()
+    }
+
     /// Writes SST to the cache and then uploads it to the remote object store.
+    ///
+    /// Both the write and the uploads stop once [WriteCache::cancel] is called.
     pub(crate) async fn write_and_upload_sst(
         &self,
         write_request: SstWriteRequest,
@@ -121,14 +150,35 @@ impl WriteCache {
         .build();
 
         // Write to FileCache.
+        let cache_path = self.file_cache.cache_file_path(parquet_key);
         let mut writer = ParquetWriter::new(
-            self.file_cache.cache_file_path(parquet_key),
+            cache_path.clone(),
             write_request.metadata,
             self.file_cache.local_store(),
             indexer,
         );
 
-        let sst_info = writer.write_all(write_request.source, write_opts).await?;
+        let written = {
+            let write = writer.write_all(write_request.source, write_opts);
+            tokio::select! {
+                res = write => Some(res),
+                _ = self.cancel.cancelled() => None,
+            }
+        };
+        let Some(written) = written else {
+            timer.stop_and_discard();
+            writer.abort().await;
+            if let Err(e) = self.file_cache.local_store().delete(&cache_path).await {
+                warn!(e; "Failed to remove the cancelled SST, path: {}", cache_path);
+            }
+            return error::UploadCancelledSnafu {
+                region_id,
+                file_id,
+                file_type: FileType::Parquet,
+            }
+            .fail();
+        };
+        let sst_info = written?;
 
         timer.stop_and_record();
 
@@ -140,28 +190,117 @@ impl WriteCache {
 
         let parquet_path = &upload_request.upload_path;
         let remote_store = &upload_request.remote_store;
-        self.upload(parquet_key, parquet_path, remote_store).await?;
+        let skip_existing = upload_request.skip_existing;
+        self.upload(
+            parquet_key,
+            parquet_path,
+            remote_store,
+            skip_existing,
+            Some(sst_info.checksum),
+            &self.cancel,
+        )
+        .await?;
 
         if sst_info.inverted_index_available {
             let puffin_key = IndexKey::new(region_id, file_id, FileType::Puffin);
             let puffin_path = &upload_request.index_upload_path;
-            self.upload(puffin_key, puffin_path, remote_store).await?;
+            // The index file has no checksum to verify.
+            self.upload(
+                puffin_key,
+                puffin_path,
+                remote_store,
+                skip_existing,
+                None,
+                &self.cancel,
+            )
+            .await?;
         }
 
         Ok(Some(sst_info))
     }
 
+    /// Uploads the files of `upload_paths` to the remote object store by [WriteCache::upload].
+    ///
+    /// Unlike uploading files one by one, a failed file doesn't stop uploading the
+    /// remaining files, so callers can retry only the failed files in the [UploadResult].
+    /// Once `cancel` is triggered, the remaining files are not uploaded and fail with
+    /// [UploadCancelled](error::Error::UploadCancelled).
+    /// Returns error only if all files fail to upload.
+    // TODO: use it to upload the output files of compaction.
+    #[allow(unused)]
+    pub(crate) async fn upload_files(
+        &self,
+        upload_paths: Vec<(IndexKey, String)>,
+        remote_store: &ObjectStore,
+        skip_existing: bool,
+        cancel: &CancellationToken,
+    ) -> Result<UploadResult> {
+        let mut result = UploadResult::default();
+        for (index_key, upload_path) in upload_paths {
+            match self
+                .upload(
+                    index_key,
+                    &upload_path,
+                    remote_store,
+                    skip_existing,
+                    None,
+                    cancel,
+                )
+                .await
+            {
+                Ok(_) => result.succeeded.push(index_key),
+                Err(e) => {
+                    warn!(
+                        e; "Failed to upload file to remote, region: {}, file: {}, upload_path: {}",
+                        index_key.region_id, index_key.file_id, upload_path
+                    );
+                    result.failed.push((index_key, e));
+                }
+            }
+        }
+
+        if result.succeeded.is_empty() && !result.failed.is_empty() {
+            // Safety: failed is not empty.
+            let (_, e) = result.failed.swap_remove(0);
+            return Err(e);
+        }
+        Ok(result)
+    }
+
     /// Uploads a Parquet file or a Puffin file to the remote object store.
+    ///
+    /// If the remote store supports append and already has a partial object at
+    /// `upload_path` matching the beginning of the file, only the remaining part of
+    /// the file is uploaded. See [WriteCache::resume_offset].
+    /// If `skip_existing` is true and the remote object already has the same size as
+    /// the local file, the file is not uploaded again.
+    /// If `cancel` is triggered, the upload stops and the remote writer is aborted,
+    /// so stores supporting abort don't keep a partial object.
+    /// If `expected_checksum` is set, the CRC32C checksum of the local file is computed
+    /// while copying it and the remote writer is aborted on mismatch, so a corrupted local
+    /// file isn't uploaded. A resumed upload also checksums the part already uploaded.
+    /// Returns the number of bytes transferred.
     async fn upload(
         &self,
         index_key: IndexKey,
         upload_path: &str,
         remote_store: &ObjectStore,
-    ) -> Result<()> {
+        skip_existing: bool,
+        expected_checksum: Option<u32>,
+        cancel: &CancellationToken,
+    ) -> Result<u64> {
         let region_id = index_key.region_id;
         let file_id = index_key.file_id;
         let file_type = index_key.file_type;
         let cache_path = self.file_cache.cache_file_path(index_key);
+        ensure!(
+            !cancel.is_cancelled(),
+            error::UploadCancelledSnafu {
+                region_id,
+                file_id,
+                file_type,
+            }
+        );
 
         let timer = FLUSH_ELAPSED
             .with_label_values(&[match file_type {
@@ -170,28 +309,106 @@ impl WriteCache {
             }])
             .start_timer();
 
-        let reader = self
-            .file_cache
-            .local_store()
-            .reader(&cache_path)
+        let local_store = self.file_cache.local_store();
+        let file_size = local_store
+            .stat(&cache_path)
             .await
-            .context(error::OpenDalSnafu)?;
+            .context(error::OpenDalSnafu)?
+            .content_length();
+
+        if skip_existing
+            && matches!(
+                remote_store.stat(upload_path).await,
+                Ok(meta) if meta.content_length() == file_size
+            )
+        {
+            timer.stop_and_discard();
+            debug!(
+                "Skip uploading existing file to remote, region: {}, file: {}, upload_path: {}",
+                region_id, file_id, upload_path
+            );
+            let index_value = IndexValue {
+                file_size: file_size as _,
+            };
+            self.file_cache.put(index_key, index_value).await;
+            return Ok(0);
+        }
+
+        let (offset, checksum) = self
+            .resume_offset(index_key, upload_path, remote_store, file_size)
+            .await?;
 
-        let mut writer = remote_store
-            .writer_with(upload_path)
-            .buffer(DEFAULT_WRITE_BUFFER_SIZE.as_bytes() as usize)
-            .concurrent(DEFAULT_WRITE_CONCURRENCY)
+        // The reader only reads the remaining part of a resumed upload, so it starts
+        // from the checksum of the uploaded part to verify the whole file.
+        let reader = local_store
+            .reader_with(&cache_path)
+            .range(offset..)
             .await
             .context(error::OpenDalSnafu)?;
+        let mut reader = ChecksumReader::new(reader, checksum);
 
-        let bytes_written =
-            futures::io::copy(reader, &mut writer)
+        let mut writer = if offset > 0 {
+            debug!(
+                "Resume uploading file to remote, region: {}, file: {}, upload_path: {}, offset: {}",
+                region_id, file_id, upload_path, offset
+            );
+            remote_store
+                .writer_with(upload_path)
+                .append(true)
+                .buffer(DEFAULT_WRITE_BUFFER_SIZE.as_bytes() as usize)
                 .await
-                .context(error::UploadSnafu {
+                .context(error::OpenDalSnafu)?
+        } else {
+            remote_store
+                .writer_with(upload_path)
+                .buffer(DEFAULT_WRITE_BUFFER_SIZE.as_bytes() as usize)
+                .concurrent(DEFAULT_WRITE_CONCURRENCY)
+                .await
+                .context(error::OpenDalSnafu)?
+        };
+
+        let copied = {
+            let copy = futures::io::copy(&mut reader, &mut writer);
+            tokio::select! {
+                res = copy => Some(res),
+                _ = cancel.cancelled() => None,
+            }
+        };
+        let Some(copied) = copied else {
+            timer.stop_and_discard();
+            if let Err(e) = writer.abort().await {
+                warn!(e; "Failed to abort the cancelled upload, upload_path: {}", upload_path);
+            }
+            return error::UploadCancelledSnafu {
+                region_id,
+                file_id,
+                file_type,
+            }
+            .fail();
+        };
+        let bytes_written = copied.context(error::UploadSnafu {
+            region_id,
+            file_id,
+            file_type,
+        })?;
+
+        if let Some(expected) = expected_checksum {
+            let actual = reader.checksum();
+            if actual != expected {
+                timer.stop_and_discard();
+                if let Err(e) = writer.abort().await {
+                    warn!(e; "Failed to abort the corrupted upload, upload_path: {}", upload_path);
+                }
+                return error::UploadChecksumMismatchSnafu {
                     region_id,
                     file_id,
                     file_type,
-                })?;
+                    expected,
+                    actual,
+                }
+                .fail();
+            }
+        }
 
         // Must close to upload all data.
         writer.close().await.context(error::OpenDalSnafu)?;
@@ -207,15 +424,128 @@ impl WriteCache {
         );
 
         let index_value = IndexValue {
-            file_size: bytes_written as _,
+            file_size: (offset + bytes_written) as _,
         };
         // Register to file cache
         self.file_cache.put(index_key, index_value).await;
 
-        Ok(())
+        Ok(bytes_written)
+    }
+
+    /// Returns the offset to resume uploading the local file of `index_key` with
+    /// `file_size` bytes to `upload_path`, and the checksum of the local file before
+    /// the offset.
+    ///
+    /// The upload is only resumed if the remote object has the same checksum as the
+    /// local file before the offset, otherwise the remote object is deleted.
+    /// Returns 0 to upload the whole file if the remote store doesn't support append,
+    /// or the remote object is absent, empty, not smaller than the local file or deleted.
+    async fn resume_offset(
+        &self,
+        index_key: IndexKey,
+        upload_path: &str,
+        remote_store: &ObjectStore,
+        file_size: u64,
+    ) -> Result<(u64, u32)> {
+        if !remote_store.info().full_capability().write_can_append {
+            return Ok((0, 0));
+        }
+        let offset = match remote_store.stat(upload_path).await {
+            Ok(meta) if meta.content_length() < file_size => meta.content_length(),
+            _ => 0,
+        };
+        if offset == 0 {
+            return Ok((0, 0));
+        }
+
+        let cache_path = self.file_cache.cache_file_path(index_key);
+        let local_checksum = prefix_checksum(
+            &self.file_cache.local_store(),
+            &cache_path,
+            offset,
+            index_key,
+        )
+        .await?;
+        let remote_checksum = prefix_checksum(remote_store, upload_path, offset, index_key).await?;
+        if local_checksum == remote_checksum {
+            return Ok((offset, local_checksum));
+        }
+
+        warn!(
+            "Remote object mismatches the local file, upload the whole file again, region: {}, file: {}, upload_path: {}",
+            index_key.region_id, index_key.file_id, upload_path
+        );
+        remote_store
+            .delete(upload_path)
+            .await
+            .context(error::OpenDalSnafu)?;
+        Ok((0, 0))
+    }
+}
+
+/// Returns the CRC32C checksum of the first `len` bytes of the object at `path`.
+async fn prefix_checksum(
+    store: &ObjectStore,
+    path: &str,
+    len: u64,
+    index_key: IndexKey,
+) -> Result<u32> {
+    let reader = store
+        .reader_with(path)
+        .range(0..len)
+        .await
+        .context(error::OpenDalSnafu)?;
+    let mut reader = ChecksumReader::new(reader, 0);
+    futures::io::copy(&mut reader, &mut futures::io::sink())
+        .await
+        .context(error::UploadSnafu {
+            region_id: index_key.region_id,
+            file_id: index_key.file_id,
+            file_type: index_key.file_type,
+        })?;
+    Ok(reader.checksum())
+}
+
+/// A reader computing the CRC32C checksum of the bytes read from the inner reader.
+struct ChecksumReader<R> {
+    inner: R,
+    checksum: u32,
+}
+
+impl<R> ChecksumReader<R> {
+    /// Creates a reader computing the checksum from the initial `checksum`.
+    fn new(inner: R, checksum: u32) -> Self {
+        Self { inner, checksum }
+    }
+
+    /// Returns the checksum of the bytes read so far.
+    fn checksum(&self) -> u32 {
+        self.checksum
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ChecksumReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = &mut *self;
+        let n = ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.checksum = crc32c::crc32c_append(this.checksum, &buf[..n]);
+        Poll::Ready(Ok(n))
     }
 }
 
+/// Result of uploading multiple files by [WriteCache::upload_files].
+#[derive(Debug, Default)]
+pub(crate) struct UploadResult {
+    /// Files uploaded successfully.
+    pub(crate) succeeded: Vec<IndexKey>,
+    /// Files failed to upload and their errors.
+    pub(crate) failed: Vec<(IndexKey, Error)>,
+}
+
 /// Request to write and upload a SST.
 pub struct SstUploadRequest {
     /// Path to upload the file.
@@ -224,48 +554,63 @@ pub struct SstUploadRequest {
     pub index_upload_path: String,
     /// Remote object store to upload.
     pub remote_store: ObjectStore,
+    /// Whether to skip uploading files that already exist in the remote store
+    /// with the same size, which makes retrying an upload idempotent.
+    pub skip_existing: bool,
 }
 
 #[cfg(test)]
 mod tests {
 
     use common_base::readable_size::ReadableSize;
-    use common_test_util::temp_dir::create_temp_dir;
+    use common_test_util::temp_dir::{create_temp_dir, TempDir};
+    use futures::StreamExt;
     use object_store::util::join_dir;
+    use tokio::sync::oneshot;
 
     use super::*;
     use crate::cache::test_util::new_fs_store;
-    use crate::sst::file::FileId;
+    use crate::read::Source;
     use crate::sst::location::{index_file_path, sst_file_path};
     use crate::test_util::sst_util::{new_batch_by_range, new_source, sst_region_metadata};
     use crate::test_util::TestEnv;
 
-    #[tokio::test]
-    async fn test_write_and_upload_sst() {
-        // TODO(QuenKar): maybe find a way to create some object server for testing,
-        // and now just use local file system to mock.
-        let mut env = TestEnv::new();
+    /// Creates a write cache whose local store is under `local_dir`, returns the write
+    /// cache, its local store and the mock remote store of `env`.
+    async fn new_write_cache(
+        env: &mut TestEnv,
+        local_dir: &TempDir,
+    ) -> (WriteCache, ObjectStore, ObjectStore) {
         let data_home = env.data_home().display().to_string();
         let mock_store = env.init_object_store_manager();
-        let file_id = FileId::random();
-        let upload_path = sst_file_path("test", file_id);
-        let index_upload_path = index_file_path("test", file_id);
         let intm_mgr = IntermediateManager::init_fs(join_dir(&data_home, "intm"))
             .await
             .unwrap();
 
-        // Create WriteCache
-        let local_dir = create_temp_dir("");
         let local_store = new_fs_store(local_dir.path().to_str().unwrap());
-        let object_store_manager = env.get_object_store_manager().unwrap();
         let write_cache = WriteCache::new(
             local_store.clone(),
-            object_store_manager,
+            env.get_object_store_manager().unwrap(),
             ReadableSize::mb(10),
             intm_mgr,
         )
         .await
         .unwrap();
+        (write_cache, local_store, mock_store)
+    }
+
+    #[tokio::test]
+    async fn test_write_and_upload_sst() {
+        // TODO(QuenKar): maybe find a way to create some object server for testing,
+        // and now just use local file system to mock.
+        let mut env = TestEnv::new();
+        let file_id = FileId::random();
+        let upload_path = sst_file_path("test", file_id);
+        let index_upload_path = index_file_path("test", file_id);
+
+        // Create WriteCache
+        let local_dir = create_temp_dir("");
+        let (write_cache, local_store, mock_store) = new_write_cache(&mut env, &local_dir).await;
 
         // Create Source
         let metadata = Arc::new(sst_region_metadata());
@@ -291,6 +636,7 @@ mod tests {
             upload_path: upload_path.clone(),
             index_upload_path: index_upload_path.clone(),
             remote_store: mock_store.clone(),
+            skip_existing: false,
         };
 
         let write_opts = WriteOptions {
@@ -299,7 +645,7 @@ mod tests {
         };
 
         // Write to cache and upload sst to mock remote store
-        write_cache
+        let sst_info = write_cache
             .write_and_upload_sst(write_request, upload_request, &write_opts)
             .await
             .unwrap()
@@ -316,6 +662,7 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(remote_data, cache_data);
+        assert_eq!(crc32c::crc32c(&remote_data), sst_info.checksum);
 
         // Check write cache contains the index key
         let index_key = IndexKey::new(region_id, file_id, FileType::Puffin);
@@ -327,5 +674,357 @@ mod tests {
             .await
             .unwrap();
         assert_eq!(remote_index_data, cache_index_data);
+
+        // The sst is present in the local store until it is evicted.
+        assert!(write_cache.contains(region_id, file_id).await);
+        write_cache.file_cache.remove(key).await;
+        assert!(!write_cache.contains(region_id, file_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_resume_upload() {
+        let mut env = TestEnv::new();
+        let local_dir = create_temp_dir("");
+        let (write_cache, local_store, mock_store) = new_write_cache(&mut env, &local_dir).await;
+
+        // Write a file to the local cache.
+        let region_id = RegionId::new(2000, 0);
+        let file_id = FileId::random();
+        let key = IndexKey::new(region_id, file_id, FileType::Parquet);
+        let data: Vec<u8> = (0..1000).map(|i| (i % 256) as u8).collect();
+        local_store
+            .write(&write_cache.file_cache.cache_file_path(key), data.clone())
+            .await
+            .unwrap();
+
+        // Simulate an interrupted upload.
+        let upload_path = sst_file_path("test", file_id);
+        mock_store
+            .write(&upload_path, data[..400].to_vec())
+            .await
+            .unwrap();
+
+        let bytes_written = write_cache
+            .upload(
+                key,
+                &upload_path,
+                &mock_store,
+                false,
+                None,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        // Only the remaining part is transferred.
+        assert_eq!(600, bytes_written);
+        assert_eq!(data, mock_store.read(&upload_path).await.unwrap());
+        assert!(write_cache.file_cache.contains_key(&key));
+
+        // Uploads the whole file if the remote object is complete.
+        let bytes_written = write_cache
+            .upload(
+                key,
+                &upload_path,
+                &mock_store,
+                false,
+                None,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(1000, bytes_written);
+        assert_eq!(data, mock_store.read(&upload_path).await.unwrap());
+
+        // Uploads the whole file if the remote object isn't a prefix of the local file.
+        let mut partial = data[..400].to_vec();
+        partial[100] ^= 1;
+        mock_store.write(&upload_path, partial).await.unwrap();
+        let bytes_written = write_cache
+            .upload(
+                key,
+                &upload_path,
+                &mock_store,
+                false,
+                None,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(1000, bytes_written);
+        assert_eq!(data, mock_store.read(&upload_path).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_upload_skip_existing() {
+        let mut env = TestEnv::new();
+        let local_dir = create_temp_dir("");
+        let (write_cache, local_store, mock_store) = new_write_cache(&mut env, &local_dir).await;
+
+        // Write two files to the local cache.
+        let region_id = RegionId::new(2000, 0);
+        let data: Vec<u8> = (0..1000).map(|i| (i % 256) as u8).collect();
+        let mut files = Vec::new();
+        for _ in 0..2 {
+            let file_id = FileId::random();
+            let key = IndexKey::new(region_id, file_id, FileType::Parquet);
+            local_store
+                .write(&write_cache.file_cache.cache_file_path(key), data.clone())
+                .await
+                .unwrap();
+            files.push((key, sst_file_path("test", file_id)));
+        }
+
+        // The first file already exists in the remote store.
+        mock_store.write(&files[0].1, data.clone()).await.unwrap();
+
+        let mut bytes_written = Vec::new();
+        for (key, upload_path) in &files {
+            let bytes = write_cache
+                .upload(
+                    *key,
+                    upload_path,
+                    &mock_store,
+                    true,
+                    None,
+                    &CancellationToken::new(),
+                )
+                .await
+                .unwrap();
+            bytes_written.push(bytes);
+            assert!(write_cache.file_cache.contains_key(key));
+            assert_eq!(data, mock_store.read(upload_path).await.unwrap());
+        }
+        assert_eq!(vec![0, 1000], bytes_written);
+
+        // Re-upload the file if the remote size mismatches.
+        mock_store.write(&files[0].1, vec![0; 1200]).await.unwrap();
+        let bytes = write_cache
+            .upload(
+                files[0].0,
+                &files[0].1,
+                &mock_store,
+                true,
+                None,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(1000, bytes);
+        assert_eq!(data, mock_store.read(&files[0].1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_write_and_upload_sst_cancelled() {
+        let mut env = TestEnv::new();
+        let local_dir = create_temp_dir("");
+        let (write_cache, local_store, mock_store) = new_write_cache(&mut env, &local_dir).await;
+
+        // A source that hangs after the first batch until the write is cancelled.
+        let (tx, rx) = oneshot::channel();
+        let stream = futures::stream::iter([Ok(new_batch_by_range(&["a", "d"], 0, 60))])
+            .chain(futures::stream::once(async move {
+                let _ = tx.send(());
+                futures::future::pending().await
+            }))
+            .boxed();
+        let metadata = Arc::new(sst_region_metadata());
+        let region_id = metadata.region_id;
+        let file_id = FileId::random();
+        let write_request = SstWriteRequest {
+            file_id,
+            metadata,
+            source: Source::Stream(stream),
+            storage: None,
+            create_inverted_index: true,
+            mem_threshold_index_create: None,
+            index_write_buffer_size: None,
+            cache_manager: Default::default(),
+        };
+        let upload_path = sst_file_path("test", file_id);
+        let upload_request = SstUploadRequest {
+            upload_path: upload_path.clone(),
+            index_upload_path: index_file_path("test", file_id),
+            remote_store: mock_store.clone(),
+            skip_existing: false,
+        };
+
+        let write_opts = WriteOptions::default();
+        let write = write_cache.write_and_upload_sst(write_request, upload_request, &write_opts);
+        let cancel = async {
+            rx.await.unwrap();
+            write_cache.cancel();
+        };
+        let (res, _) = tokio::join!(write, cancel);
+        let err = res.unwrap_err();
+        assert!(matches!(err, Error::UploadCancelled { .. }), "{err:?}");
+
+        // The writer is aborted, so neither the local nor the remote store keeps the SST.
+        let key = IndexKey::new(region_id, file_id, FileType::Parquet);
+        let cache_path = write_cache.file_cache.cache_file_path(key);
+        assert!(!local_store.is_exist(&cache_path).await.unwrap());
+        assert!(!write_cache.file_cache.contains_key(&key));
+        assert!(!mock_store.is_exist(&upload_path).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_upload_files() {
+        let mut env = TestEnv::new();
+        let local_dir = create_temp_dir("");
+        let (write_cache, local_store, mock_store) = new_write_cache(&mut env, &local_dir).await;
+
+        // Only the first and the last files exist in the local cache.
+        let region_id = RegionId::new(2000, 0);
+        let data: Vec<u8> = (0..1000).map(|i| (i % 256) as u8).collect();
+        let keys = (0..3)
+            .map(|_| IndexKey::new(region_id, FileId::random(), FileType::Parquet))
+            .collect::<Vec<_>>();
+        for key in [keys[0], keys[2]] {
+            local_store
+                .write(&write_cache.file_cache.cache_file_path(key), data.clone())
+                .await
+                .unwrap();
+        }
+        let upload_paths = keys
+            .iter()
+            .map(|key| (*key, sst_file_path("test", key.file_id)))
+            .collect::<Vec<_>>();
+
+        let result = write_cache
+            .upload_files(
+                upload_paths.clone(),
+                &mock_store,
+                false,
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(vec![keys[0], keys[2]], result.succeeded);
+        assert_eq!(1, result.failed.len());
+        assert_eq!(keys[1], result.failed[0].0);
+        assert_eq!(data, mock_store.read(&upload_paths[2].1).await.unwrap());
+
+        // Returns error if all files fail.
+        assert!(write_cache
+            .upload_files(
+                vec![upload_paths[1].clone()],
+                &mock_store,
+                false,
+                &CancellationToken::new()
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_upload_cancelled() {
+        let mut env = TestEnv::new();
+        let local_dir = create_temp_dir("");
+        let (write_cache, local_store, mock_store) = new_write_cache(&mut env, &local_dir).await;
+
+        let region_id = RegionId::new(2000, 0);
+        let file_id = FileId::random();
+        let key = IndexKey::new(region_id, file_id, FileType::Parquet);
+        local_store
+            .write(&write_cache.file_cache.cache_file_path(key), vec![1; 1000])
+            .await
+            .unwrap();
+        let upload_path = sst_file_path("test", file_id);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = write_cache
+            .upload(key, &upload_path, &mock_store, false, None, &cancel)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UploadCancelled { .. }), "{err:?}");
+        let err = write_cache
+            .upload_files(
+                vec![(key, upload_path.clone())],
+                &mock_store,
+                false,
+                &cancel,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::UploadCancelled { .. }), "{err:?}");
+        // Nothing is written to the remote store.
+        assert!(!mock_store.is_exist(&upload_path).await.unwrap());
+        assert!(!write_cache.file_cache.contains_key(&key));
+    }
+
+    #[tokio::test]
+    async fn test_upload_checksum() {
+        let mut env = TestEnv::new();
+        let local_dir = create_temp_dir("");
+        let (write_cache, local_store, mock_store) = new_write_cache(&mut env, &local_dir).await;
+
+        let region_id = RegionId::new(2000, 0);
+        let file_id = FileId::random();
+        let key = IndexKey::new(region_id, file_id, FileType::Parquet);
+        let data: Vec<u8> = (0..100_000).map(|i| (i % 251) as u8).collect();
+        let checksum = crc32c::crc32c(&data);
+        local_store
+            .write(&write_cache.file_cache.cache_file_path(key), data.clone())
+            .await
+            .unwrap();
+        let upload_path = sst_file_path("test", file_id);
+
+        let err = write_cache
+            .upload(
+                key,
+                &upload_path,
+                &mock_store,
+                false,
+                Some(checksum ^ 1),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::UploadChecksumMismatch { expected, actual, .. }
+                    if expected == checksum ^ 1 && actual == checksum
+            ),
+            "{err:?}"
+        );
+        assert!(!write_cache.file_cache.contains_key(&key));
+        // Removes the partial object in case the store doesn't support abort.
+        mock_store.delete(&upload_path).await.unwrap();
+
+        let bytes_written = write_cache
+            .upload(
+                key,
+                &upload_path,
+                &mock_store,
+                false,
+                Some(checksum),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(data.len() as u64, bytes_written);
+        assert_eq!(data, mock_store.read(&upload_path).await.unwrap());
+        assert!(write_cache.file_cache.contains_key(&key));
+
+        // A resumed upload verifies the checksum of the whole file.
+        for (expected, succeeded) in [(checksum ^ 1, false), (checksum, true)] {
+            mock_store
+                .write(&upload_path, data[..40_000].to_vec())
+                .await
+                .unwrap();
+            let res = write_cache
+                .upload(
+                    key,
+                    &upload_path,
+                    &mock_store,
+                    false,
+                    Some(expected),
+                    &CancellationToken::new(),
+                )
+                .await;
+            assert_eq!(succeeded, res.is_ok(), "{res:?}");
+        }
+        assert_eq!(data, mock_store.read(&upload_path).await.unwrap());
     }
 }
@@ -37,6 +37,7 @@ pub fn new_file_handle(
             file_size: 0,
             available_indexes: Default::default(),
             index_file_size: 0,
+            checksum: None,
         },
         file_purger,
     )
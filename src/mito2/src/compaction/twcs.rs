@@ -356,6 +356,7 @@ impl TwcsCompactionTask {
                             .then(|| SmallVec::from_iter([IndexType::InvertedIndex]))
                             .unwrap_or_default(),
                         index_file_size: sst_info.index_file_size,
+                        checksum: Some(sst_info.checksum),
                     });
                 Ok(file_meta_opt)
             });
@@ -537,6 +537,36 @@ pub enum Error {
         location: Location,
     },
 
+    #[snafu(display(
+        "Upload cancelled, region_id: {}, file_id: {}, file_type: {:?}",
+        region_id,
+        file_id,
+        file_type,
+    ))]
+    UploadCancelled {
+        region_id: RegionId,
+        file_id: FileId,
+        file_type: FileType,
+        location: Location,
+    },
+
+    #[snafu(display(
+        "Checksum mismatch while uploading, region_id: {}, file_id: {}, file_type: {:?}, expected: {}, actual: {}",
+        region_id,
+        file_id,
+        file_type,
+        expected,
+        actual,
+    ))]
+    UploadChecksumMismatch {
+        region_id: RegionId,
+        file_id: FileId,
+        file_type: FileType,
+        expected: u32,
+        actual: u32,
+        location: Location,
+    },
+
     #[snafu(display("Failed to filter record batch"))]
     FilterRecordBatch {
         source: common_recordbatch::error::Error,
@@ -640,6 +670,8 @@ impl ErrorExt for Error {
             StaleLogEntry { .. } => StatusCode::Unexpected,
             FilterRecordBatch { source, .. } => source.status_code(),
             Upload { .. } => StatusCode::StorageUnavailable,
+            UploadCancelled { .. } => StatusCode::Cancelled,
+            UploadChecksumMismatch { .. } => StatusCode::Unexpected,
         }
     }
 
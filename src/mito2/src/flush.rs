@@ -360,6 +360,7 @@ impl RegionFlushTask {
                     .then(|| SmallVec::from_iter([IndexType::InvertedIndex]))
                     .unwrap_or_default(),
                 index_file_size: sst_info.index_file_size,
+                checksum: Some(sst_info.checksum),
             };
             file_metas.push(file_meta);
         }
@@ -100,6 +100,9 @@ pub struct FileMeta {
     pub available_indexes: SmallVec<[IndexType; 4]>,
     /// Size of the index file.
     pub index_file_size: u64,
+    /// CRC32C checksum of the file, `None` if unknown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<u32>,
 }
 
 /// Type of index.
@@ -255,6 +258,7 @@ mod tests {
             file_size: 0,
             available_indexes: SmallVec::from_iter([IndexType::InvertedIndex]),
             index_file_size: 0,
+            checksum: None,
         }
     }
 
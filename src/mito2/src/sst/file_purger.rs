@@ -141,6 +141,7 @@ mod tests {
                     file_size: 4096,
                     available_indexes: Default::default(),
                     index_file_size: 0,
+                    checksum: None,
                 },
                 file_purger,
             );
@@ -192,6 +193,7 @@ mod tests {
                     file_size: 4096,
                     available_indexes: SmallVec::from_iter([IndexType::InvertedIndex]),
                     index_file_size: 4096,
+                    checksum: None,
                 },
                 file_purger,
             );
@@ -63,6 +63,8 @@ pub struct SstInfo {
     pub time_range: FileTimeRange,
     /// File size in bytes.
     pub file_size: u64,
+    /// CRC32C checksum of the file.
+    pub checksum: u32,
     /// Number of rows.
     pub num_rows: usize,
     /// File Meta Data
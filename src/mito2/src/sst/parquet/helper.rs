@@ -15,7 +15,8 @@
 use std::ops::Range;
 use std::sync::Arc;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use futures::{Stream, StreamExt};
 use object_store::{ErrorKind, ObjectStore};
 use parquet::basic::ColumnOrder;
 use parquet::file::metadata::{FileMetaData, ParquetMetaData, RowGroupMetaData};
@@ -26,6 +27,14 @@ use snafu::ResultExt;
 use crate::error;
 use crate::error::Result;
 
+/// The max size of a single read request, some object stores cap the size of a request.
+const MAX_REQUEST_SIZE: u64 = 5 * 1024 * 1024 * 1024;
+
+/// Sequential reads smaller than this size in total are read inline instead of
+/// spawning to the blocking pool, as the spawn overhead dominates for tiny reads.
+/// Inline reads block the runtime worker, so the threshold should stay small.
+const INLINE_READ_THRESHOLD: u64 = 8 * 1024;
+
 // Refer to https://github.com/apache/arrow-rs/blob/7e134f4d277c0b62c27529fc15a4739de3ad0afd/parquet/src/file/footer.rs#L74-L90
 /// Convert [format::FileMetaData] to [ParquetMetaData]
 pub fn parse_parquet_metadata(t_file_metadata: format::FileMetaData) -> Result<ParquetMetaData> {
@@ -88,29 +97,363 @@ fn parse_column_orders(
     }
 }
 
+// Refer to https://github.com/apache/arrow-rs/blob/802ed428f87051fdca31180430ddb0ecb2f60e8b/object_store/src/util.rs#L85-L121
+/// Merges `raw_ranges` into fewer, larger ranges to reduce the number of reads.
+///
+/// Two ranges are merged if the gap between them is no larger than `coalesce`
+/// and the merged range doesn't exceed `max_range_size`. A single raw range that
+/// is already larger than `max_range_size` is kept as is.
+///
+/// Merged ranges smaller than `min_range_size` are padded at the end up to
+/// `min_range_size`, without overlapping the next range. The caller should clamp
+/// the last range to the file size. A `min_range_size` of 0 disables padding.
+///
+/// The returned ranges are sorted by start offset.
+pub fn merge_ranges(
+    raw_ranges: &[Range<u64>],
+    coalesce: u64,
+    max_range_size: u64,
+    min_range_size: u64,
+) -> Vec<Range<u64>> {
+    if raw_ranges.is_empty() {
+        return vec![];
+    }
+
+    // Fast path: sorted input without any ranges to merge, e.g. parquet page ranges.
+    let mut merged = if raw_ranges.windows(2).all(|pair| {
+        pair[0].start <= pair[1].start
+            && !should_merge(&pair[0], &pair[1], coalesce, max_range_size)
+    }) {
+        raw_ranges.to_vec()
+    } else {
+        let mut ranges = raw_ranges.to_vec();
+        ranges.sort_unstable_by_key(|range| range.start);
+        merge_sorted_ranges(ranges, coalesce, max_range_size)
+    };
+
+    if min_range_size > 0 {
+        for i in 0..merged.len() {
+            let limit = merged.get(i + 1).map_or(u64::MAX, |next| next.start);
+            let range = &mut merged[i];
+            if range.end - range.start < min_range_size {
+                range.end = range
+                    .start
+                    .saturating_add(min_range_size)
+                    .min(limit)
+                    .max(range.end);
+            }
+        }
+    }
+
+    merged
+}
+
+/// Merges the non-empty `ranges` sorted by start offset.
+fn merge_sorted_ranges(
+    ranges: Vec<Range<u64>>,
+    coalesce: u64,
+    max_range_size: u64,
+) -> Vec<Range<u64>> {
+    let mut merged = Vec::with_capacity(ranges.len());
+    let mut iter = ranges.into_iter();
+    // Safety: the ranges are not empty.
+    let mut current = iter.next().unwrap();
+    for range in iter {
+        if should_merge(&current, &range, coalesce, max_range_size) {
+            current.end = current.end.max(range.end);
+        } else {
+            merged.push(current);
+            current = range;
+        }
+    }
+    merged.push(current);
+
+    merged
+}
+
+/// Returns true if `range`, which doesn't start before `current`, should be merged
+/// into `current`.
+fn should_merge(
+    current: &Range<u64>,
+    range: &Range<u64>,
+    coalesce: u64,
+    max_range_size: u64,
+) -> bool {
+    let within_gap = range
+        .start
+        .checked_sub(current.end)
+        .map(|gap| gap <= coalesce)
+        // The range overlaps with the current range.
+        .unwrap_or(true);
+    let merged_end = current.end.max(range.end);
+    within_gap && merged_end - current.start <= max_range_size
+}
+
+/// Further merges the sorted `ranges` returned by [merge_ranges] until there are
+/// at most `max_output_ranges` ranges, by merging the ranges separated by the smallest gaps.
+///
+/// This bounds the number of requests regardless of the input, at the cost of
+/// reading the bytes in the merged gaps. Merged ranges may exceed the `max_range_size`
+/// of [merge_ranges]. At least one range is returned for non-empty input.
+pub fn limit_range_count(ranges: Vec<Range<u64>>, max_output_ranges: usize) -> Vec<Range<u64>> {
+    let max_output_ranges = max_output_ranges.max(1);
+    if ranges.len() <= max_output_ranges {
+        return ranges;
+    }
+
+    // Merging two adjacent ranges doesn't change the other gaps, so we can
+    // merge the smallest gaps at once.
+    let mut gaps = ranges
+        .windows(2)
+        .enumerate()
+        .map(|(i, pair)| (pair[1].start.saturating_sub(pair[0].end), i))
+        .collect::<Vec<_>>();
+    gaps.sort_unstable();
+    let mut merge_next = vec![false; ranges.len()];
+    for (_, i) in &gaps[..ranges.len() - max_output_ranges] {
+        merge_next[*i] = true;
+    }
+
+    let mut limited = Vec::with_capacity(max_output_ranges);
+    let mut iter = ranges.into_iter().enumerate();
+    // Safety: the ranges are not empty.
+    let (mut i, mut current) = iter.next().unwrap();
+    for (j, range) in iter {
+        if merge_next[i] {
+            current.end = current.end.max(range.end);
+        } else {
+            limited.push(current);
+            current = range;
+        }
+        i = j;
+    }
+    limited.push(current);
+
+    limited
+}
+
+/// Splits each range larger than `max_request_size` into contiguous chunks no larger
+/// than `max_request_size`.
+///
+/// Returns the chunks of each range in the same order as `ranges`, concatenating the
+/// chunks of a range yields the range itself. A `max_request_size` of 0 disables splitting.
+pub fn split_ranges(ranges: &[Range<u64>], max_request_size: u64) -> Vec<Vec<Range<u64>>> {
+    ranges
+        .iter()
+        .map(|range| {
+            if max_request_size == 0 || range.end - range.start <= max_request_size {
+                return vec![range.clone()];
+            }
+            (range.start..range.end)
+                .step_by(max_request_size as usize)
+                .map(|start| start..start.saturating_add(max_request_size).min(range.end))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the smallest range that covers all the `ranges`, from the min start to
+/// the max end. Return `None` if `ranges` is empty.
+// TODO: use it to decide whether to fetch ranges into a shared buffer.
+#[allow(unused)]
+pub fn covering_range(ranges: &[Range<u64>]) -> Option<Range<u64>> {
+    let start = ranges.iter().map(|range| range.start).min()?;
+    // Safety: the ranges are not empty.
+    let end = ranges.iter().map(|range| range.end).max().unwrap();
+    Some(start..end)
+}
+
+/// Returns true if any two of the `ranges` share some bytes. Adjacent ranges like
+/// `0..10` and `10..20` and empty ranges don't overlap.
+// TODO: use it to validate the page index before merging ranges.
+#[allow(unused)]
+pub fn has_overlapping_ranges(ranges: &[Range<u64>]) -> bool {
+    let mut sorted = ranges
+        .iter()
+        .filter(|range| range.start < range.end)
+        .collect::<Vec<_>>();
+    sorted.sort_unstable_by_key(|range| range.start);
+    // If a range overlaps any range before it, it overlaps the previous one as the
+    // ranges are sorted by start.
+    sorted
+        .windows(2)
+        .any(|window| window[1].start < window[0].end)
+}
+
+/// Returns the part of `merged_buffer`, which holds the data of `merged_range`,
+/// corresponding to the `wanted` range. The returned [Bytes] shares the buffer.
+///
+/// # Panics
+/// Panics if `wanted` is not within `merged_range` or the buffer is shorter than
+/// `merged_range`.
+pub fn slice_merged(
+    merged_buffer: &Bytes,
+    merged_range: &Range<u64>,
+    wanted: &Range<u64>,
+) -> Bytes {
+    assert!(
+        merged_range.start <= wanted.start && wanted.end <= merged_range.end,
+        "range {wanted:?} is out of the merged range {merged_range:?}"
+    );
+    assert!(
+        merged_buffer.len() as u64 >= merged_range.end - merged_range.start,
+        "buffer length {} is shorter than the merged range {merged_range:?}",
+        merged_buffer.len()
+    );
+    let start = (wanted.start - merged_range.start) as usize;
+    let end = (wanted.end - merged_range.start) as usize;
+    merged_buffer.slice(start..end)
+}
+
+/// Estimates the read amplification of fetching `raw_ranges` coalesced by
+/// [merge_ranges], which is the ratio of the transferred bytes to the requested bytes,
+/// without doing any I/O.
+///
+/// The ratio is at least 1.0 for disjoint ranges, and 1.0 for empty input.
+// TODO: use it to tune the coalesce size.
+#[allow(unused)]
+pub fn estimate_read_amplification(
+    raw_ranges: &[Range<u64>],
+    coalesce: u64,
+    max_range_size: u64,
+) -> f64 {
+    let merged = merge_ranges(raw_ranges, coalesce, max_range_size, 0);
+    let stats = FetchStats::new(raw_ranges, &merged);
+    if stats.requested == 0 {
+        return 1.0;
+    }
+    stats.transferred as f64 / stats.requested as f64
+}
+
+/// Statistics of a fetch that coalesces byte ranges.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FetchStats {
+    /// Total bytes of the requested ranges.
+    pub requested: u64,
+    /// Total bytes actually read from the object store.
+    pub transferred: u64,
+}
+
+impl FetchStats {
+    /// Creates the stats of fetching `requested_ranges` by reading `fetch_ranges`.
+    pub fn new(requested_ranges: &[Range<u64>], fetch_ranges: &[Range<u64>]) -> FetchStats {
+        let total_size = |ranges: &[Range<u64>]| -> u64 {
+            ranges.iter().map(|range| range.end - range.start).sum()
+        };
+        FetchStats {
+            requested: total_size(requested_ranges),
+            transferred: total_size(fetch_ranges),
+        }
+    }
+}
+
 /// Fetches data from object store.
 /// If the object store supports blocking, use sequence blocking read.
 /// Otherwise, use concurrent read.
+///
+/// The returned data is in the same order as `ranges`, the i-th item is the data
+/// of `ranges[i]`, regardless of how the ranges are read internally. Ranges can be
+/// unsorted or overlap with each other.
 pub async fn fetch_byte_ranges(
     file_path: &str,
     object_store: ObjectStore,
     ranges: &[Range<u64>],
 ) -> object_store::Result<Vec<Bytes>> {
-    if object_store.info().full_capability().blocking {
-        fetch_ranges_seq(file_path, object_store, ranges).await
+    fetch_byte_ranges_with_max_request_size(file_path, object_store, ranges, MAX_REQUEST_SIZE).await
+}
+
+/// Fetches data from object store, ranges larger than `max_request_size` are
+/// read by multiple requests and reassembled.
+/// The returned data is in the same order as `ranges`.
+async fn fetch_byte_ranges_with_max_request_size(
+    file_path: &str,
+    object_store: ObjectStore,
+    ranges: &[Range<u64>],
+    max_request_size: u64,
+) -> object_store::Result<Vec<Bytes>> {
+    let split = split_ranges(ranges, max_request_size);
+    if split.iter().all(|chunks| chunks.len() == 1) {
+        return fetch_ranges(file_path, object_store, ranges).await;
+    }
+
+    let chunk_ranges = split.iter().flatten().cloned().collect::<Vec<_>>();
+    let mut chunks = fetch_ranges(file_path, object_store, &chunk_ranges)
+        .await?
+        .into_iter();
+    let results = split
+        .iter()
+        .zip(ranges)
+        .map(|(range_chunks, range)| {
+            if range_chunks.len() == 1 {
+                // Safety: the number of fetched chunks equals the number of chunk ranges.
+                return chunks.next().unwrap();
+            }
+            let mut data = BytesMut::with_capacity((range.end - range.start) as usize);
+            for chunk in chunks.by_ref().take(range_chunks.len()) {
+                data.extend_from_slice(&chunk);
+            }
+            data.freeze()
+        })
+        .collect();
+    Ok(results)
+}
+
+/// Returns true if the ranges of the object store are fetched by sequence
+/// blocking read instead of concurrent async read.
+pub fn prefers_blocking_fetch(object_store: &ObjectStore) -> bool {
+    object_store.info().full_capability().blocking
+}
+
+/// Fetches the ranges by sequence blocking read if the object store supports
+/// blocking, otherwise by concurrent read.
+async fn fetch_ranges(
+    file_path: &str,
+    object_store: ObjectStore,
+    ranges: &[Range<u64>],
+) -> object_store::Result<Vec<Bytes>> {
+    if prefers_blocking_fetch(&object_store) {
+        fetch_ranges_seq(file_path, object_store, ranges, INLINE_READ_THRESHOLD).await
     } else {
         fetch_ranges_concurrent(file_path, object_store, ranges).await
     }
 }
 
-/// Fetches data from object store sequentially
+/// Fetches data from object store lazily as a stream.
+///
+/// Each item is the data of the range in the same order as `ranges`, ranges are
+/// read one by one when the stream is polled, so only one range is held in memory
+/// by the stream at a time.
+// TODO: use it to read large column chunks.
+#[allow(unused)]
+pub fn fetch_byte_ranges_stream(
+    file_path: &str,
+    object_store: ObjectStore,
+    ranges: &[Range<u64>],
+) -> impl Stream<Item = object_store::Result<Bytes>> {
+    let file_path = file_path.to_string();
+    futures::stream::iter(ranges.to_vec()).then(move |range| {
+        let future_read = object_store.read_with(&file_path);
+        async move {
+            let data = future_read.range(range.start..range.end).await?;
+            Ok::<_, object_store::Error>(Bytes::from(data))
+        }
+    })
+}
+
+/// Fetches data from object store sequentially, in the same order as `ranges`.
+///
+/// Reads smaller than `inline_threshold` bytes in total are done inline, which blocks the
+/// current runtime worker until the reads finish. Otherwise the reads are spawned to the
+/// blocking pool. An `inline_threshold` of 0 disables inline reads.
 async fn fetch_ranges_seq(
     file_path: &str,
     object_store: ObjectStore,
     ranges: &[Range<u64>],
+    inline_threshold: u64,
 ) -> object_store::Result<Vec<Bytes>> {
     let block_object_store = object_store.blocking();
     let file_path = file_path.to_string();
+    let total_size: u64 = ranges.iter().map(|range| range.end - range.start).sum();
     let ranges = ranges.to_vec();
 
     let f = move || -> object_store::Result<Vec<Bytes>> {
@@ -126,10 +469,15 @@ async fn fetch_ranges_seq(
             .collect::<object_store::Result<Vec<_>>>()
     };
 
-    maybe_spawn_blocking(f).await
+    if total_size < inline_threshold {
+        f()
+    } else {
+        maybe_spawn_blocking(f).await
+    }
 }
 
 /// Fetches data from object store concurrently.
+/// The returned data is in the same order as `ranges` though reads may complete out of order.
 async fn fetch_ranges_concurrent(
     file_path: &str,
     object_store: ObjectStore,
@@ -169,3 +517,383 @@ where
 fn new_task_join_error(e: tokio::task::JoinError) -> object_store::Error {
     object_store::Error::new(ErrorKind::Unexpected, "tokio task join failed").set_source(e)
 }
+
+#[cfg(test)]
+mod tests {
+    use common_test_util::temp_dir::create_temp_dir;
+    use futures::{FutureExt, TryStreamExt};
+    use object_store::services::S3;
+
+    use super::*;
+    use crate::cache::test_util::new_fs_store;
+
+    #[test]
+    fn test_merge_ranges() {
+        assert!(merge_ranges(&[], 10, 100, 0).is_empty());
+
+        // Adjacent ranges are merged.
+        assert_eq!(vec![0..10], merge_ranges(&[0..5, 5..10], 0, 100, 0));
+        // Ranges within the coalesce gap are merged.
+        assert_eq!(vec![0..20], merge_ranges(&[0..5, 10..20], 5, 100, 0));
+        // Ranges beyond the coalesce gap are kept.
+        assert_eq!(vec![0..5, 11..20], merge_ranges(&[0..5, 11..20], 5, 100, 0));
+        // Unsorted and overlapping ranges.
+        assert_eq!(
+            vec![0..15, 30..40],
+            merge_ranges(&[30..40, 5..15, 0..8], 1, 100, 0)
+        );
+        // Merged ranges don't exceed the max range size.
+        assert_eq!(
+            vec![0..10, 10..20, 20..30],
+            merge_ranges(&[0..10, 10..20, 20..30], 0, 15, 0)
+        );
+        // A single large range is kept as is.
+        assert_eq!(
+            vec![0..100, 100..110],
+            merge_ranges(&[0..100, 100..110], 0, 50, 0)
+        );
+    }
+
+    #[test]
+    fn test_split_ranges() {
+        const GB: u64 = 1024 * 1024 * 1024;
+
+        let split = split_ranges(&[0..6 * GB], 2 * GB);
+        assert_eq!(vec![vec![0..2 * GB, 2 * GB..4 * GB, 4 * GB..6 * GB]], split);
+        // Chunks reconcatenate to the range exactly.
+        let chunks = &split[0];
+        assert_eq!(0, chunks[0].start);
+        assert_eq!(6 * GB, chunks[chunks.len() - 1].end);
+        assert!(chunks.windows(2).all(|pair| pair[0].end == pair[1].start));
+
+        // Small ranges are kept and the last chunk may be smaller.
+        assert_eq!(
+            vec![vec![0..10], vec![100..110, 110..120, 120..125]],
+            split_ranges(&[0..10, 100..125], 10)
+        );
+        // Splitting is disabled.
+        assert_eq!(vec![vec![0..100]], split_ranges(&[0..100], 0));
+        assert!(split_ranges(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_estimate_read_amplification() {
+        assert_eq!(1.0, estimate_read_amplification(&[], 10, 100));
+        // Adjacent ranges are merged without extra bytes.
+        assert_eq!(1.0, estimate_read_amplification(&[0..5, 5..10], 0, 100));
+        // The gap of 5 bytes is transferred: 20 / 15.
+        let ratio = estimate_read_amplification(&[0..5, 10..20], 5, 100);
+        assert!((ratio - 20.0 / 15.0).abs() < f64::EPSILON, "{ratio}");
+        // Ranges beyond the coalesce gap are kept.
+        assert_eq!(1.0, estimate_read_amplification(&[0..5, 11..20], 5, 100));
+        // Merged ranges don't exceed the max range size.
+        assert_eq!(
+            1.0,
+            estimate_read_amplification(&[0..10, 20..30, 40..50], 10, 15)
+        );
+        let ratio = estimate_read_amplification(&[0..10, 20..30, 40..50], 10, 100);
+        assert!((ratio - 50.0 / 30.0).abs() < f64::EPSILON, "{ratio}");
+    }
+
+    #[test]
+    fn test_slice_merged() {
+        let data = Bytes::from((0..100u8).collect::<Vec<_>>());
+        let merged_range = 1000..1100;
+        assert_eq!(
+            &data[10..20],
+            &slice_merged(&data, &merged_range, &(1010..1020))[..]
+        );
+        assert_eq!(data, slice_merged(&data, &merged_range, &merged_range));
+        assert!(slice_merged(&data, &merged_range, &(1100..1100)).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_slice_merged_out_of_range() {
+        let data = Bytes::from((0..100u8).collect::<Vec<_>>());
+        slice_merged(&data, &(1000..1100), &(990..1010));
+    }
+
+    #[test]
+    fn test_covering_range() {
+        assert_eq!(None, covering_range(&[]));
+        assert_eq!(Some(5..10), covering_range(&[5..10]));
+        assert_eq!(
+            Some(0..4096),
+            covering_range(&[1000..1500, 0..10, 4000..4096, 10..20])
+        );
+        // A range inside another range.
+        assert_eq!(Some(0..100), covering_range(&[10..20, 0..100]));
+    }
+
+    #[test]
+    fn test_has_overlapping_ranges() {
+        assert!(!has_overlapping_ranges(&[]));
+        assert!(!has_overlapping_ranges(&[0..10]));
+        // Overlapping.
+        assert!(has_overlapping_ranges(&[0..10, 5..15]));
+        assert!(has_overlapping_ranges(&[20..30, 0..10, 9..11]));
+        assert!(has_overlapping_ranges(&[0..100, 10..20]));
+        assert!(has_overlapping_ranges(&[0..100, 50..60, 10..20]));
+        assert!(has_overlapping_ranges(&[0..10, 0..10]));
+        // Adjacent.
+        assert!(!has_overlapping_ranges(&[10..20, 0..10, 20..30]));
+        // Disjoint.
+        assert!(!has_overlapping_ranges(&[
+            1000..1500,
+            0..10,
+            4000..4096,
+            20..30
+        ]));
+        // Empty ranges don't overlap.
+        assert!(!has_overlapping_ranges(&[0..10, 5..5]));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_byte_ranges_with_max_request_size() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "ranges.bin";
+        let data = (0..4096u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        object_store.write(file_path, data.clone()).await.unwrap();
+
+        let ranges = [1000..1500, 0..10, 4000..4096, 10..20];
+        let actual = fetch_byte_ranges_with_max_request_size(file_path, object_store, &ranges, 64)
+            .await
+            .unwrap();
+        let expect = ranges
+            .iter()
+            .map(|range| Bytes::copy_from_slice(&data[range.start as usize..range.end as usize]))
+            .collect::<Vec<_>>();
+        assert_eq!(expect, actual);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_byte_ranges_order() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "ranges.bin";
+        let data = (0..65536u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        object_store.write(file_path, data.clone()).await.unwrap();
+
+        // Shuffled and overlapping ranges.
+        let ranges = [
+            60000..65536,
+            0..100,
+            50..20000,
+            100..100,
+            30000..30001,
+            0..100,
+            10000..40000,
+        ];
+        let expect = ranges
+            .iter()
+            .map(|range| Bytes::copy_from_slice(&data[range.start as usize..range.end as usize]))
+            .collect::<Vec<_>>();
+
+        let actual = fetch_byte_ranges(file_path, object_store.clone(), &ranges)
+            .await
+            .unwrap();
+        assert_eq!(expect, actual);
+        let actual = fetch_ranges_seq(
+            file_path,
+            object_store.clone(),
+            &ranges,
+            INLINE_READ_THRESHOLD,
+        )
+        .await
+        .unwrap();
+        assert_eq!(expect, actual);
+        let actual = fetch_ranges_concurrent(file_path, object_store.clone(), &ranges)
+            .await
+            .unwrap();
+        assert_eq!(expect, actual);
+        let actual =
+            fetch_byte_ranges_with_max_request_size(file_path, object_store.clone(), &ranges, 1000)
+                .await
+                .unwrap();
+        assert_eq!(expect, actual);
+        let actual = fetch_byte_ranges_stream(file_path, object_store, &ranges)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_prefers_blocking_fetch() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        assert!(object_store.info().full_capability().blocking);
+        assert!(prefers_blocking_fetch(&object_store));
+
+        let mut builder = S3::default();
+        builder
+            .bucket("test")
+            .region("us-east-1")
+            .endpoint("http://127.0.0.1:9000")
+            .access_key_id("access_key")
+            .secret_access_key("secret_key");
+        let object_store = ObjectStore::new(builder).unwrap().finish();
+        assert!(!object_store.info().full_capability().blocking);
+        assert!(!prefers_blocking_fetch(&object_store));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_ranges_seq_inline() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "ranges.bin";
+        let data = (0..4096u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        object_store.write(file_path, data.clone()).await.unwrap();
+
+        // Reads below the threshold are done inline, so the first poll completes the fetch.
+        let ranges = [100..101, 0..10];
+        let expect = vec![
+            Bytes::copy_from_slice(&data[100..101]),
+            Bytes::copy_from_slice(&data[0..10]),
+        ];
+        let actual = fetch_ranges_seq(file_path, object_store.clone(), &ranges, 12)
+            .now_or_never()
+            .unwrap()
+            .unwrap();
+        assert_eq!(expect, actual);
+
+        // Reads reaching the threshold are spawned to the blocking pool.
+        let actual = fetch_ranges_seq(file_path, object_store, &ranges, 11)
+            .await
+            .unwrap();
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn test_merge_ranges_fast_path() {
+        // The result equals merging the sorted ranges, whether the fast path is taken or not.
+        let check = |ranges: &[Range<u64>], coalesce, max_range_size| {
+            let mut sorted = ranges.to_vec();
+            sorted.sort_unstable_by_key(|range| range.start);
+            assert_eq!(
+                merge_sorted_ranges(sorted, coalesce, max_range_size),
+                merge_ranges(ranges, coalesce, max_range_size, 0),
+                "ranges: {ranges:?}"
+            );
+        };
+
+        // Sorted and disjoint ranges are returned as is.
+        let ranges = (0..100u64)
+            .map(|i| i * 100..i * 100 + 10)
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, merge_ranges(&ranges, 10, 1000, 0));
+        check(&ranges, 10, 1000);
+        check(&[0..10, 10..20, 20..30], 0, 15);
+        // Sorted but mergeable ranges.
+        check(&[0..5, 10..20, 50..60, 58..70], 5, 100);
+        // Overlapping ranges.
+        check(&[0..50, 10..20, 40..60, 100..110], 0, 100);
+        check(&[0..50, 10..20, 40..60], 0, 30);
+        // Unsorted ranges.
+        check(&[100..110, 0..10, 5..30], 5, 100);
+        // Ranges are still padded.
+        assert_eq!(
+            vec![0..100, 500..600],
+            merge_ranges(&[0..10, 500..510], 0, 1000, 100)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_byte_ranges_stream() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "ranges.bin";
+        let data = (0..4096u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        object_store.write(file_path, data).await.unwrap();
+
+        let ranges = [1000..1500, 0..10, 4000..4096, 10..20];
+        let expect = fetch_byte_ranges(file_path, object_store.clone(), &ranges)
+            .await
+            .unwrap();
+        let actual = fetch_byte_ranges_stream(file_path, object_store.clone(), &ranges)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(expect, actual);
+
+        // Reading a missing file fails.
+        let mut stream = Box::pin(fetch_byte_ranges_stream(
+            "missing.bin",
+            object_store,
+            &ranges,
+        ));
+        assert!(stream.next().await.unwrap().is_err());
+    }
+
+    #[test]
+    fn test_merge_ranges_min_range_size() {
+        // Small ranges are padded.
+        assert_eq!(
+            vec![0..100, 500..600],
+            merge_ranges(&[0..10, 500..510], 0, 1000, 100)
+        );
+        // Large ranges are unchanged.
+        assert_eq!(
+            vec![0..200, 500..800],
+            merge_ranges(&[0..200, 500..800], 0, 1000, 100)
+        );
+        // Padding doesn't overlap the next range.
+        assert_eq!(
+            vec![0..50, 50..150],
+            merge_ranges(&[0..10, 50..60], 0, 40, 100)
+        );
+        // Ranges are merged before padding.
+        assert_eq!(vec![0..100], merge_ranges(&[0..10, 12..20], 5, 1000, 100));
+    }
+
+    #[test]
+    fn test_limit_range_count() {
+        assert!(limit_range_count(vec![], 8).is_empty());
+        // No need to merge.
+        assert_eq!(
+            vec![0..10, 20..30],
+            limit_range_count(vec![0..10, 20..30], 2)
+        );
+        // Merges the smallest gaps first.
+        assert_eq!(
+            vec![0..30, 100..110],
+            limit_range_count(vec![0..10, 20..30, 100..110], 2)
+        );
+        assert_eq!(
+            vec![0..110],
+            limit_range_count(vec![0..10, 20..30, 100..110], 0)
+        );
+
+        // 100 scattered ranges with different gaps.
+        let mut start = 0;
+        let ranges = (0..100)
+            .map(|i| {
+                let range = start..start + 10;
+                start += 10 + (1 + i * 37 % 101) * 100;
+                range
+            })
+            .collect::<Vec<_>>();
+        let merged = merge_ranges(&ranges, 0, u64::MAX, 0);
+        assert_eq!(100, merged.len());
+        let limited = limit_range_count(merged, 8);
+        assert!(limited.len() <= 8);
+        // All raw ranges are still covered.
+        for range in &ranges {
+            assert!(limited
+                .iter()
+                .any(|r| r.start <= range.start && range.end <= r.end));
+        }
+        assert!(limited.windows(2).all(|w| w[0].end < w[1].start));
+    }
+
+    #[test]
+    fn test_fetch_stats() {
+        let ranges = [0..10, 20..30, 25..40];
+        let fetch_ranges = merge_ranges(&ranges, 10, 100, 0);
+        let stats = FetchStats::new(&ranges, &fetch_ranges);
+        assert_eq!(35, stats.requested);
+        assert_eq!(40, stats.transferred);
+    }
+}
@@ -12,17 +12,42 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::ops::Range;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use datatypes::arrow::datatypes::SchemaRef;
 use object_store::ObjectStore;
+use parquet::arrow::parquet_to_arrow_schema;
 use parquet::file::footer::{decode_footer, decode_metadata};
 use parquet::file::metadata::ParquetMetaData;
 use parquet::file::FOOTER_SIZE;
 use snafu::ResultExt;
 
 use crate::error::{self, Result};
+use crate::sst::parquet::helper::{
+    fetch_byte_ranges, limit_range_count, merge_ranges, slice_merged, FetchStats,
+};
 
 /// The estimated size of the footer and metadata need to read from the end of parquet file.
 const DEFAULT_PREFETCH_SIZE: u64 = 64 * 1024;
 
+/// The max prefetch size recommended by [MetadataLoader::recommended_prefetch].
+const MAX_RECOMMENDED_PREFETCH_SIZE: u64 = 1024 * 1024;
+
+/// Page index ranges whose gap is no larger than this size are fetched in one read.
+const PAGE_INDEX_COALESCE_SIZE: u64 = 1024 * 1024;
+
+/// The max size of a coalesced read for page index ranges.
+const PAGE_INDEX_MAX_RANGE_SIZE: u64 = 8 * 1024 * 1024;
+
+/// The max number of reads to fetch page index ranges.
+const PAGE_INDEX_MAX_FETCH_RANGES: usize = 16;
+
+/// The min size of a read for page index ranges, small reads are padded to this size
+/// to amortize the request overhead.
+const PAGE_INDEX_MIN_RANGE_SIZE: u64 = 256 * 1024;
+
 /// Load the metadata of parquet file in an async way.
 pub(crate) struct MetadataLoader<'a> {
     // An object store that supports async read
@@ -31,6 +56,8 @@ pub(crate) struct MetadataLoader<'a> {
     file_path: &'a str,
     // The size of parquet file
     file_size: u64,
+    // The max bytes of the first row group to read ahead, 0 means disabled
+    read_ahead_size: u64,
 }
 
 impl<'a> MetadataLoader<'a> {
@@ -40,13 +67,33 @@ impl<'a> MetadataLoader<'a> {
             object_store,
             file_path,
             file_size,
+            read_ahead_size: 0,
         }
     }
 
+    /// Set the max bytes of the first row group's column chunks to read ahead
+    /// by [MetadataLoader::load_with_read_ahead].
+    // TODO: use it in the parquet reader to reuse the prefetched bytes.
+    #[allow(unused)]
+    pub fn with_read_ahead(mut self, read_ahead_size: u64) -> Self {
+        self.read_ahead_size = read_ahead_size;
+        self
+    }
+
+    /// Returns the recommended size to prefetch from the end of a parquet file of
+    /// `file_size` bytes, so the metadata are likely to be read in one shot.
+    ///
+    /// Larger files usually have larger metadata, the size is 1% of the file size
+    /// clamped between [DEFAULT_PREFETCH_SIZE] and [MAX_RECOMMENDED_PREFETCH_SIZE].
+    pub fn recommended_prefetch(file_size: u64) -> u64 {
+        (file_size / 100).clamp(DEFAULT_PREFETCH_SIZE, MAX_RECOMMENDED_PREFETCH_SIZE)
+    }
+
     /// Async load the metadata of parquet file.
     ///
-    /// Read [DEFAULT_PREFETCH_SIZE] from the end of parquet file at first, if File Metadata is in the
-    /// read range, decode it and return [ParquetMetaData], otherwise, read again to get the rest of the metadata.
+    /// Read [MetadataLoader::recommended_prefetch] bytes from the end of parquet file at first, if File
+    /// Metadata is in the read range, decode it and return [ParquetMetaData], otherwise, read again to get
+    /// the rest of the metadata.
     ///
     /// Parquet File Format:
     /// ```text
@@ -67,6 +114,13 @@ impl<'a> MetadataLoader<'a> {
     ///
     /// Refer to https://github.com/apache/arrow-rs/blob/093a10e46203be1a0e94ae117854701bf58d4c79/parquet/src/arrow/async_reader/metadata.rs#L55-L106
     pub async fn load(&self) -> Result<ParquetMetaData> {
+        let (metadata, _) = self.load_with_prefetch().await?;
+        Ok(metadata)
+    }
+
+    /// Async load the metadata of parquet file like [MetadataLoader::load], along with
+    /// the start offset and bytes of the data prefetched from the end of the file.
+    async fn load_with_prefetch(&self) -> Result<(ParquetMetaData, (u64, Bytes))> {
         let object_store = &self.object_store;
         let path = self.file_path;
         let file_size = self.get_file_size().await?;
@@ -80,12 +134,13 @@ impl<'a> MetadataLoader<'a> {
         }
 
         // Prefetch bytes for metadata from the end and process the footer
-        let buffer_start = file_size.saturating_sub(DEFAULT_PREFETCH_SIZE);
+        let buffer_start = file_size.saturating_sub(Self::recommended_prefetch(file_size));
         let buffer = object_store
             .read_with(path)
             .range(buffer_start..file_size)
             .await
             .context(error::OpenDalSnafu)?;
+        let buffer = Bytes::from(buffer);
         let buffer_len = buffer.len();
 
         let mut footer = [0; 8];
@@ -99,6 +154,14 @@ impl<'a> MetadataLoader<'a> {
             .build()
         })? as u64;
 
+        if metadata_len == 0 {
+            return error::InvalidParquetSnafu {
+                file: path,
+                reason: "metadata length is zero",
+            }
+            .fail();
+        }
+
         if file_size - (FOOTER_SIZE as u64) < metadata_len {
             return error::InvalidParquetSnafu {
                 file: path,
@@ -121,7 +184,7 @@ impl<'a> MetadataLoader<'a> {
                     }
                     .build()
                 })?;
-            Ok(metadata)
+            Ok((metadata, (buffer_start, buffer)))
         } else {
             // The metadata is out of buffer, need to make a second read
             let metadata_start = file_size - metadata_len - FOOTER_SIZE as u64;
@@ -138,8 +201,122 @@ impl<'a> MetadataLoader<'a> {
                 }
                 .build()
             })?;
-            Ok(metadata)
+            Ok((metadata, (buffer_start, buffer)))
+        }
+    }
+
+    /// Async load the metadata of parquet file by [MetadataLoader::load], then read ahead
+    /// at most `read_ahead_size` bytes from the start of the first row group's column chunks.
+    ///
+    /// Returns the metadata along with the read ahead range and bytes, the latter is None if
+    /// read ahead is disabled or the file has no row groups. Callers usually read the
+    /// first row group right after loading the metadata, so small files can save a round trip.
+    /// If the range is already prefetched with the metadata, the bytes are sliced from the
+    /// prefetched data without another read.
+    #[allow(unused)]
+    pub async fn load_with_read_ahead(
+        &self,
+    ) -> Result<(ParquetMetaData, Option<(Range<u64>, Bytes)>)> {
+        let (metadata, (buffer_start, buffer)) = self.load_with_prefetch().await?;
+        if self.read_ahead_size == 0 || metadata.num_row_groups() == 0 {
+            return Ok((metadata, None));
+        }
+
+        let (start, end) = metadata
+            .row_group(0)
+            .columns()
+            .iter()
+            .map(|column| {
+                let (start, len) = column.byte_range();
+                (start, start + len)
+            })
+            .fold((u64::MAX, 0), |(start, end), (col_start, col_end)| {
+                (start.min(col_start), end.max(col_end))
+            });
+        if start >= end {
+            return Ok((metadata, None));
+        }
+        let range = start..end.min(start.saturating_add(self.read_ahead_size));
+        let buffer_range = buffer_start..buffer_start + buffer.len() as u64;
+        let data = if buffer_range.start <= range.start && range.end <= buffer_range.end {
+            slice_merged(&buffer, &buffer_range, &range)
+        } else {
+            let data = self
+                .object_store
+                .read_with(self.file_path)
+                .range(range.clone())
+                .await
+                .context(error::OpenDalSnafu)?;
+            Bytes::from(data)
+        };
+
+        Ok((metadata, Some((range, data))))
+    }
+
+    /// Async load the metadata and return the `created_by` of the parquet file, which
+    /// tells the application that wrote the file.
+    // TODO: use it to check the compatibility of files written by different versions.
+    #[allow(unused)]
+    pub async fn load_created_by(&self) -> Result<Option<String>> {
+        let metadata = self.load().await?;
+        Ok(metadata.file_metadata().created_by().map(str::to_string))
+    }
+
+    /// Async load the metadata and return the arrow schema of the parquet file.
+    // TODO: use it to check the compatibility of files written by different versions.
+    #[allow(unused)]
+    pub async fn load_arrow_schema(&self) -> Result<SchemaRef> {
+        let metadata = self.load().await?;
+        let file_metadata = metadata.file_metadata();
+        let schema = parquet_to_arrow_schema(
+            file_metadata.schema_descr(),
+            file_metadata.key_value_metadata(),
+        )
+        .context(error::ReadParquetSnafu {
+            path: self.file_path,
+        })?;
+        Ok(Arc::new(schema))
+    }
+
+    /// Async load the given byte ranges of the parquet file, such as the column index
+    /// and offset index ranges of the page index.
+    ///
+    /// These ranges are usually many small adjacent ranges at the end of the file, so we
+    /// coalesce them by [merge_ranges] and fetch them in a few reads. The returned bytes
+    /// are in the same order as `ranges`, along with the [FetchStats] of the reads.
+    // TODO: use it to load the page index once the page index is enabled.
+    #[allow(unused)]
+    pub async fn load_page_index_ranges(
+        &self,
+        ranges: &[Range<u64>],
+    ) -> Result<(Vec<Bytes>, FetchStats)> {
+        let file_size = self.get_file_size().await?;
+        let merged_ranges = page_index_fetch_ranges(ranges, file_size);
+        let stats = FetchStats::new(ranges, &merged_ranges);
+        let merged_bytes =
+            fetch_byte_ranges(self.file_path, self.object_store.clone(), &merged_ranges)
+                .await
+                .context(error::OpenDalSnafu)?;
+
+        // Visits the ranges by start offset, so the merged range containing the current
+        // range is at or after the one containing the previous range.
+        let mut order = (0..ranges.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|i| (ranges[*i].start, ranges[*i].end));
+        let mut bytes = vec![Bytes::new(); ranges.len()];
+        let mut cursor = 0;
+        for i in order {
+            let range = &ranges[i];
+            // Empty ranges may not be covered by any merged range.
+            if range.start >= range.end {
+                continue;
+            }
+            while merged_ranges[cursor].start > range.start || merged_ranges[cursor].end < range.end
+            {
+                cursor += 1;
+            }
+            bytes[i] = slice_merged(&merged_bytes[cursor], &merged_ranges[cursor], range);
         }
+        Ok((bytes, stats))
     }
 
     /// Get the size of parquet file.
@@ -156,3 +333,238 @@ impl<'a> MetadataLoader<'a> {
         Ok(file_size)
     }
 }
+
+/// Returns the ranges to fetch for the page index `ranges` of a file of `file_size` bytes.
+fn page_index_fetch_ranges(ranges: &[Range<u64>], file_size: u64) -> Vec<Range<u64>> {
+    let mut merged = merge_ranges(
+        ranges,
+        PAGE_INDEX_COALESCE_SIZE,
+        PAGE_INDEX_MAX_RANGE_SIZE,
+        PAGE_INDEX_MIN_RANGE_SIZE,
+    );
+    // Only the last range may be padded beyond the file size, but it still covers
+    // the requested ranges.
+    let end_limit = ranges
+        .iter()
+        .map(|range| range.end)
+        .max()
+        .unwrap_or(0)
+        .max(file_size);
+    if let Some(last) = merged.last_mut() {
+        last.end = last.end.min(end_limit);
+    }
+    limit_range_count(merged, PAGE_INDEX_MAX_FETCH_RANGES)
+}
+
+#[cfg(test)]
+mod tests {
+    use common_test_util::temp_dir::create_temp_dir;
+
+    use super::*;
+    use crate::cache::test_util::{new_fs_store, parquet_file_data};
+
+    /// Returns a synthetic page index layout: the column index ranges of `num_columns`
+    /// columns followed by their offset index ranges, starting at `start`.
+    fn page_index_layout(start: u64, num_columns: u64) -> Vec<Range<u64>> {
+        let mut ranges = Vec::with_capacity(num_columns as usize * 2);
+        let mut offset = start;
+        // Column index and offset index lengths vary between columns.
+        for len in (0..num_columns)
+            .map(|i| 20 + i % 7)
+            .chain((0..num_columns).map(|i| 8 + i % 3))
+        {
+            ranges.push(offset..offset + len);
+            offset += len;
+        }
+        ranges
+    }
+
+    #[test]
+    fn test_page_index_fetch_ranges() {
+        let ranges = page_index_layout(4096, 32);
+        assert_eq!(64, ranges.len());
+        // All adjacent index ranges are fetched in one read.
+        let end = ranges.last().unwrap().end;
+        let fetch_ranges = page_index_fetch_ranges(&ranges, end);
+        assert_eq!(vec![4096..end], fetch_ranges);
+
+        // Two index regions far away from each other.
+        let mut ranges = page_index_layout(0, 8);
+        ranges.extend(page_index_layout(16 * 1024 * 1024, 8));
+        let file_size = 32 * 1024 * 1024;
+        let fetch_ranges = page_index_fetch_ranges(&ranges, file_size);
+        // The small reads are padded to the min range size.
+        assert_eq!(
+            vec![
+                0..PAGE_INDEX_MIN_RANGE_SIZE,
+                16 * 1024 * 1024..16 * 1024 * 1024 + PAGE_INDEX_MIN_RANGE_SIZE
+            ],
+            fetch_ranges
+        );
+
+        // The padding doesn't exceed the file size.
+        assert_eq!(vec![0..100], page_index_fetch_ranges(&[0..10, 50..60], 100));
+        // The requested ranges are still covered.
+        assert_eq!(
+            vec![0..200],
+            page_index_fetch_ranges(&[0..10, 150..200], 100)
+        );
+        assert!(page_index_fetch_ranges(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn test_recommended_prefetch() {
+        // Small files.
+        assert_eq!(
+            DEFAULT_PREFETCH_SIZE,
+            MetadataLoader::recommended_prefetch(0)
+        );
+        assert_eq!(
+            DEFAULT_PREFETCH_SIZE,
+            MetadataLoader::recommended_prefetch(1024)
+        );
+        assert_eq!(
+            DEFAULT_PREFETCH_SIZE,
+            MetadataLoader::recommended_prefetch(6 * 1024 * 1024)
+        );
+        // 1% of the file size.
+        assert_eq!(
+            512 * 1024,
+            MetadataLoader::recommended_prefetch(50 * 1024 * 1024)
+        );
+        // Large files.
+        assert_eq!(
+            MAX_RECOMMENDED_PREFETCH_SIZE,
+            MetadataLoader::recommended_prefetch(1024 * 1024 * 1024)
+        );
+        assert_eq!(
+            MAX_RECOMMENDED_PREFETCH_SIZE,
+            MetadataLoader::recommended_prefetch(u64::MAX)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_zero_metadata_len() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "zero_metadata.parquet";
+        // A footer with zero metadata length.
+        let mut data = b"PAR1".to_vec();
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(b"PAR1");
+        object_store.write(file_path, data.clone()).await.unwrap();
+
+        let loader = MetadataLoader::new(object_store, file_path, data.len() as u64);
+        let err = loader.load().await.unwrap_err();
+        assert!(err.to_string().contains("metadata length is zero"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_load_page_index_ranges() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "page_index.bin";
+        let data = (0..4096u32).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        object_store.write(file_path, data.clone()).await.unwrap();
+
+        let ranges = page_index_layout(1024, 16);
+        let loader = MetadataLoader::new(object_store, file_path, data.len() as u64);
+        let (bytes, stats) = loader.load_page_index_ranges(&ranges).await.unwrap();
+        assert_eq!(ranges.len(), bytes.len());
+        // The adjacent ranges are fetched in one read, padded up to the end of the file.
+        let requested = ranges.last().unwrap().end - ranges[0].start;
+        assert_eq!(
+            FetchStats {
+                requested,
+                transferred: data.len() as u64 - ranges[0].start,
+            },
+            stats
+        );
+        for (range, bytes) in ranges.iter().zip(bytes) {
+            assert_eq!(
+                &data[range.start as usize..range.end as usize],
+                bytes.as_ref()
+            );
+        }
+
+        // The gap between two ranges and the padding are also transferred.
+        let ranges = [100..200, 300..350];
+        let (bytes, stats) = loader.load_page_index_ranges(&ranges).await.unwrap();
+        assert_eq!(&data[300..350], bytes[1].as_ref());
+        assert_eq!(150, stats.requested);
+        assert_eq!(data.len() as u64 - 100, stats.transferred);
+
+        // Unsorted and overlapping ranges are returned in the requested order.
+        let ranges = [300..350, 100..200, 150..160, 300..350, 120..120, 4000..4000];
+        let (bytes, _) = loader.load_page_index_ranges(&ranges).await.unwrap();
+        for (range, bytes) in ranges.iter().zip(bytes) {
+            assert_eq!(
+                &data[range.start as usize..range.end as usize],
+                bytes.as_ref()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_created_by_and_arrow_schema() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "created_by.parquet";
+        let data = parquet_file_data();
+        object_store.write(file_path, data.clone()).await.unwrap();
+
+        let loader = MetadataLoader::new(object_store, file_path, data.len() as u64);
+        // The file is written by the ArrowWriter with default properties.
+        let created_by = loader.load_created_by().await.unwrap();
+        assert_eq!(
+            Some(parquet::file::properties::DEFAULT_CREATED_BY),
+            created_by.as_deref()
+        );
+
+        let schema = loader.load_arrow_schema().await.unwrap();
+        assert_eq!(1, schema.fields().len());
+        assert_eq!("col", schema.field(0).name());
+        assert_eq!(
+            &datatypes::arrow::datatypes::DataType::Int64,
+            schema.field(0).data_type()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_with_read_ahead() {
+        let dir = create_temp_dir("");
+        let object_store = new_fs_store(dir.path().to_str().unwrap());
+        let file_path = "read_ahead.parquet";
+        let data = parquet_file_data();
+        object_store.write(file_path, data.clone()).await.unwrap();
+
+        // Read ahead is disabled by default.
+        let loader = MetadataLoader::new(object_store.clone(), file_path, data.len() as u64);
+        let (_, read_ahead) = loader.load_with_read_ahead().await.unwrap();
+        assert!(read_ahead.is_none());
+
+        let loader = loader.with_read_ahead(16);
+        let (metadata, read_ahead) = loader.load_with_read_ahead().await.unwrap();
+        assert_eq!(1, metadata.num_row_groups());
+        let (range, bytes) = read_ahead.unwrap();
+        let (start, _) = metadata.row_group(0).column(0).byte_range();
+        assert_eq!(start..start + 16, range);
+        let expect = object_store
+            .read_with(file_path)
+            .range(range)
+            .await
+            .unwrap();
+        assert_eq!(expect, bytes.as_ref());
+
+        // The read ahead range doesn't exceed the row group.
+        let loader = MetadataLoader::new(object_store, file_path, 0).with_read_ahead(1024 * 1024);
+        let (metadata, read_ahead) = loader.load_with_read_ahead().await.unwrap();
+        let (range, bytes) = read_ahead.unwrap();
+        let (start, len) = metadata.row_group(0).column(0).byte_range();
+        assert_eq!(start..start + len, range);
+        assert_eq!(
+            &data[range.start as usize..range.end as usize],
+            bytes.as_ref()
+        );
+    }
+}
@@ -123,7 +123,8 @@ impl ParquetWriter {
             return Ok(None);
         }
 
-        let (file_meta, file_size) = buffered_writer.close().await.context(WriteBufferSnafu)?;
+        let (file_meta, file_size, checksum) =
+            buffered_writer.close().await.context(WriteBufferSnafu)?;
 
         // Safety: num rows > 0 so we must have min/max.
         let time_range = stats.time_range.unwrap();
@@ -135,6 +136,7 @@ impl ParquetWriter {
         Ok(Some(SstInfo {
             time_range,
             file_size,
+            checksum,
             num_rows: stats.num_rows,
             file_metadata: Some(Arc::new(parquet_metadata)),
             inverted_index_available,
@@ -142,6 +144,11 @@ impl ParquetWriter {
         }))
     }
 
+    /// Aborts the index creation after the write is cancelled.
+    pub async fn abort(&mut self) {
+        self.indexer.abort().await;
+    }
+
     /// Customizes per-column config according to schema and maybe column cardinality.
     fn customize_column_config(
         builder: WriterPropertiesBuilder,
@@ -98,6 +98,7 @@ impl VersionControlBuilder {
                 file_size: 0, // We don't care file size.
                 available_indexes: Default::default(),
                 index_file_size: 0,
+                checksum: None,
             },
         );
         self
@@ -140,6 +141,7 @@ pub(crate) fn apply_edit(
                 file_size: 0, // We don't care file size.
                 available_indexes: Default::default(),
                 index_file_size: 0,
+                checksum: None,
             }
         })
         .collect();
@@ -170,6 +170,10 @@ impl WorkerGroup {
     pub(crate) async fn stop(&self) -> Result<()> {
         info!("Stop region worker group");
 
+        // Cancels the uploads so the background jobs don't wait for them.
+        if let Some(write_cache) = self.cache_manager.write_cache() {
+            write_cache.cancel();
+        }
         // Stops the scheduler gracefully.
         self.scheduler.stop(true).await?;
 